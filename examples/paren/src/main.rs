@@ -1,7 +1,5 @@
 use mpl::parser::Parser;
-use mpl::rules::{RightRule, Rules};
 use mpl::span::StartAndLenSpan;
-use mpl::symbols::U8SliceTerminal;
 use mpl::trees::AST;
 use mpl_macro::Parse;
 
@@ -9,18 +7,7 @@ use mpl_macro::Parse;
 #[mplg = "parentheses.mplg"]
 pub struct ParenParser;
 
-impl<'a> Rules<U8SliceTerminal<'a>, ParenVariable> for ParenRules {
-    fn get(
-        &self,
-        variable: &ParenVariable,
-    ) -> Option<&RightRule<U8SliceTerminal<'a>, ParenVariable>> {
-        Some(match variable {
-            ParenVariable::Open => &Self::Open_RULE,
-            ParenVariable::Parentheses => &Self::Parentheses_RULE,
-            ParenVariable::Close => &Self::Close_RULE,
-        })
-    }
-}
+type ParenAST = AST<ParenVariable, StartAndLenSpan<u32, u16>, ()>;
 
 enum ParseResult {
     Ok,
@@ -49,10 +36,7 @@ fn main() {
         // all of the span
         let all_of_the_span =
             StartAndLenSpan::<u32, u16>::from_start_len(0, input_data.len() as u16);
-        let result: Result<
-            AST<ParenVariable, StartAndLenSpan<u32, u16>, ()>,
-            AST<ParenVariable, StartAndLenSpan<u32, u16>, ()>,
-        > = parser.parse(
+        let result: Result<ParenAST, _> = parser.parse(
             input_data,
             &ParenRules,
             &ParenVariable::Open,
@@ -0,0 +1,240 @@
+use crate::mplg::{MplgOutput, MplgParseError};
+use mpl::analysis::{left_recursions, reachable};
+use mpl::error::{FarthestFailure, ParseErrorKind};
+use mpl::span::StartAndLenSpan;
+use mpl::symbols::E;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Line and column in the grammar, which start from 1.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Location<'a> {
+    source: &'a str,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Location<'a> {
+    /// Returns the location of `offset` bytes in `data`.
+    pub fn new(source: &'a str, data: &[u8], offset: usize) -> Self {
+        let before = String::from_utf8_lossy(&data[..offset.min(data.len())]);
+        let line = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+
+        Self {
+            source,
+            line,
+            column,
+        }
+    }
+
+    /// Returns the location of the start of `span` in `data`.
+    pub fn of_span(source: &'a str, data: &[u8], span: &StartAndLenSpan<u32, u32>) -> Self {
+        Self::new(source, data, span.start as usize)
+    }
+}
+
+impl fmt::Display for Location<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.source, self.line, self.column)
+    }
+}
+
+/// Returns the message of the error of `parse_mplg`, like `expected '=' at my.mplg:1:3`.
+pub fn parse_error_message(source: &str, data: &[u8], error: MplgParseError) -> String {
+    let location = |pos: u32| Location::new(source, data, pos as usize);
+
+    let farthest_failure = error.farthest_failure;
+    match (farthest_failure.pos, error.kind) {
+        (Some(pos), _) => FarthestFailure {
+            pos: Some(location(pos)),
+            stack: farthest_failure.stack,
+            expected: farthest_failure.expected,
+        }
+        .to_string(),
+        (None, ParseErrorKind::PartialMatch { hi, .. }) => {
+            format!("parsed only up to {}", location(hi))
+        }
+        (None, _) => format!("failed to parse {}", source),
+    }
+}
+
+/// Checks that each variable has exactly one rule.
+///
+/// Returns messages of all duplicate and undefined variables in order of the grammar.
+pub fn check_variables(source: &str, data: &[u8], lines: &[MplgOutput]) -> Result<(), Vec<String>> {
+    let rules = lines.iter().filter_map(|line| match line {
+        MplgOutput::Rule(rule, spans) => Some((rule, spans)),
+        _ => None,
+    });
+    let location = |span| Location::of_span(source, data, span);
+
+    let mut messages = Vec::new();
+    let mut variables = HashMap::new();
+    for (rule, spans) in rules.clone() {
+        match variables.get(rule.value) {
+            Some(first) => messages.push(format!(
+                "duplicate rule for variable `{}` at {}, first defined at {}",
+                rule.value,
+                location(&spans.variable),
+                location(*first)
+            )),
+            None => {
+                variables.insert(rule.value, &spans.variable);
+            }
+        }
+    }
+
+    for (rule, spans) in rules {
+        for (e, span) in [
+            (&rule.equal.first.lhs, &spans.first_lhs),
+            (&rule.equal.first.rhs, &spans.first_rhs),
+            (&rule.equal.second.0, &spans.second),
+        ] {
+            match e {
+                E::V(v) if !variables.contains_key(v) => {
+                    messages.push(format!("undefined variable `{}` at {}", v, location(span)));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    if messages.is_empty() {
+        Ok(())
+    } else {
+        Err(messages)
+    }
+}
+
+/// Checks that rules have no left recursion and that every variable is reachable from the first one.
+///
+/// Expects variables checked by `check_variables`.
+/// Returns messages of all left-recursive cycles and unreachable variables in order of the grammar.
+pub fn check_rules(source: &str, data: &[u8], lines: &[MplgOutput]) -> Result<(), Vec<String>> {
+    let rules = lines
+        .iter()
+        .filter_map(|line| match line {
+            MplgOutput::Rule(rule, spans) => Some((rule, spans)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let location = |variable: &&str| {
+        let (_, spans) = rules
+            .iter()
+            .find(|(rule, _)| rule.value == *variable)
+            .unwrap();
+        Location::of_span(source, data, &spans.variable)
+    };
+
+    let variables = rules.iter().map(|(rule, _)| rule.value).collect::<Vec<_>>();
+    let right_rules = rules
+        .iter()
+        .map(|(rule, _)| (rule.value, rule.equal.clone()))
+        .collect::<HashMap<_, _>>();
+
+    let mut messages = Vec::new();
+    for cycle in left_recursions(&right_rules, &variables) {
+        let names = cycle
+            .iter()
+            .map(|v| format!("`{}`", v))
+            .collect::<Vec<_>>()
+            .join(", ");
+        messages.push(format!(
+            "left recursion of {} at {}",
+            names,
+            location(&cycle[0])
+        ));
+    }
+
+    if let Some(start_variable) = variables.first() {
+        let reachable = reachable(&right_rules, start_variable);
+        for variable in variables.iter().filter(|v| !reachable.contains(*v)) {
+            messages.push(format!(
+                "variable `{}` at {} is not reachable from `{}`",
+                variable,
+                location(variable),
+                start_variable
+            ));
+        }
+    }
+
+    if messages.is_empty() {
+        Ok(())
+    } else {
+        Err(messages)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mplg::parse_mplg;
+
+    fn check(data: &[u8]) -> Result<(), Vec<String>> {
+        match parse_mplg(data) {
+            Ok(ast) => {
+                let lines = ast.into_original().expect("Lines").into_lines();
+                check_variables("my.mplg", data, &lines)?;
+                check_rules("my.mplg", data, &lines)
+            }
+            Err(e) => Err(vec![parse_error_message("my.mplg", data, e)]),
+        }
+    }
+
+    #[test]
+    fn location() {
+        let data = "A = B C / D\r\n// こんにちは\nE".as_bytes();
+        assert_eq!(Location::new("my.mplg", data, 0).to_string(), "my.mplg:1:1");
+        assert_eq!(Location::new("my.mplg", data, 4).to_string(), "my.mplg:1:5");
+        assert_eq!(
+            Location::new("my.mplg", data, data.len() - 1).to_string(),
+            "my.mplg:3:1"
+        );
+        assert_eq!(
+            Location::new("my.mplg", data, data.len() - 5).to_string(),
+            "my.mplg:2:8"
+        );
+    }
+
+    #[test]
+    fn diagnostics() {
+        assert_eq!(check(b"A = () () / f\n"), Ok(()));
+        assert_eq!(
+            check(b"A = () () / f\nB = () () f\n"),
+            Err(vec!["expected \" / \" at my.mplg:2:10".to_string()])
+        );
+        assert_eq!(
+            check(b"A = B () / f\nA = () () / f\n"),
+            Err(vec![
+                "duplicate rule for variable `A` at my.mplg:2:1, first defined at my.mplg:1:1"
+                    .to_string(),
+                "undefined variable `B` at my.mplg:1:5".to_string(),
+            ])
+        );
+        assert_eq!(
+            check(b"A = () () / f\n// B\nB = { 'b' } A / C\nA = C D / B\n"),
+            Err(vec![
+                "duplicate rule for variable `A` at my.mplg:4:1, first defined at my.mplg:1:1"
+                    .to_string(),
+                "undefined variable `C` at my.mplg:3:17".to_string(),
+                "undefined variable `C` at my.mplg:4:5".to_string(),
+                "undefined variable `D` at my.mplg:4:7".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn analysis() {
+        assert_eq!(check(b"S = A () / f\nA = { 'a' } S / ()\n"), Ok(()));
+        assert_eq!(
+            check(b"S = A () / f\nA = B { 'a' } / { 'a' }\nB = () A / f\nC = { 'c' } () / f\nD = ! D / ()\n"),
+            Err(vec![
+                "left recursion of `A`, `B` at my.mplg:2:1".to_string(),
+                "left recursion of `D` at my.mplg:5:1".to_string(),
+                "variable `C` at my.mplg:4:1 is not reachable from `S`".to_string(),
+                "variable `D` at my.mplg:5:1 is not reachable from `S`".to_string(),
+            ])
+        );
+    }
+}
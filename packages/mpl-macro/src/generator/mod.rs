@@ -1,45 +1,104 @@
 use crate::mplg::parse_mplg;
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use quote::{format_ident, quote};
 use std::fs::File;
 use std::io::Read;
 use std::path::Path;
-use syn::{parse2, Attribute, DeriveInput, Generics, Ident, Lit, Meta};
+use syn::{
+    parse2, parse_quote, Attribute, DeriveInput, Generics, Ident, Lit, Meta, NestedMeta, Type,
+};
 
+pub use self::diagnostic::{check_rules, check_variables, parse_error_message};
 pub use self::parser::generate_parser;
 pub use self::rules::generate_rules;
 pub use self::variable::generate_variable;
 
+mod diagnostic;
 mod parser;
 mod rules;
 mod variable;
 
-#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Debug)]
 enum GrammarData {
-    Mplg(Vec<u8>),
+    /// `source` is the file path or `mplg_inline`,
+    /// and `span` is the span of the attribute for diagnostics.
+    Mplg {
+        data: Vec<u8>,
+        source: String,
+        span: Span,
+    },
     None,
 }
 
+/// Types of input and terminal symbols given by `#[mplg(input = "...", terminal = "...")]`.
+///
+/// `'a` in the types is the lifetime of input and terminal symbols.
+#[derive(Clone)]
+struct GrammarTypes {
+    input: Type,
+    terminal: Type,
+}
+
+impl Default for GrammarTypes {
+    fn default() -> Self {
+        Self {
+            input: parse_quote!([u8]),
+            terminal: parse_quote!(::mpl::symbols::U8SliceTerminal<'a>),
+        }
+    }
+}
+
 pub fn derive_parser(input: TokenStream) -> TokenStream {
     let input = parse2(input).unwrap();
-    let (ref parser_ident, _generics, grammar_data) = parse_derive(input);
+    let (ref parser_ident, _generics, attrs) = parse_derive(input);
     let ident = parser_ident.to_string().replace("Parser", "");
     let rules_ident = &format_ident!("{}Rules", ident);
     let variable_ident = &format_ident!("{}Variable", ident);
 
-    match grammar_data {
-        Ok(grammar_data) => {
+    match attrs {
+        Ok((grammar_data, types)) => {
             match grammar_data {
-                // mplg = \"...\"
-                GrammarData::Mplg(data) => {
-                    let lines = parse_mplg(&data)
-                        .unwrap()
-                        .into_original()
-                        .expect("Lines")
-                        .into_lines();
+                // mplg = \"...\" or mplg_inline = \"...\"
+                GrammarData::Mplg { data, source, span } => {
+                    let lines = match parse_mplg(&data) {
+                        Ok(ast) => ast.into_original().expect("Lines").into_lines(),
+                        Err(e) => {
+                            let message = parse_error_message(&source, &data, e);
+                            return syn::Error::new(span, message).to_compile_error();
+                        }
+                    };
+                    if let Err(messages) = check_variables(&source, &data, &lines)
+                        .and_then(|_| check_rules(&source, &data, &lines))
+                    {
+                        return messages
+                            .into_iter()
+                            .map(|message| syn::Error::new(span, message).to_compile_error())
+                            .collect();
+                    }
                     let variable = generate_variable(variable_ident, &lines);
-                    let rules = generate_rules(rules_ident, variable_ident, &lines);
-                    let parser = generate_parser(parser_ident, rules_ident, variable_ident);
+                    let rules = match generate_rules(
+                        rules_ident,
+                        variable_ident,
+                        &types.terminal,
+                        &source,
+                        &data,
+                        &lines,
+                    ) {
+                        Ok(rules) => rules,
+                        Err(messages) => {
+                            return messages
+                                .into_iter()
+                                .map(|message| syn::Error::new(span, message).to_compile_error())
+                                .collect();
+                        }
+                    };
+                    let parser = generate_parser(
+                        parser_ident,
+                        rules_ident,
+                        variable_ident,
+                        &types.input,
+                        &types.terminal,
+                    );
 
                     quote! {
                         #variable
@@ -59,37 +118,93 @@ fn parse_derive(
 ) -> (
     Ident,
     Generics,
-    Result<GrammarData, proc_macro2::TokenStream>,
+    Result<(GrammarData, GrammarTypes), proc_macro2::TokenStream>,
 ) {
     let parser_ident = input.ident;
     let generics = input.generics;
 
-    let attr = match input.attrs.len() {
-        0 => Ok(GrammarData::None),
-        1 => get_grammar_data(&input.attrs[0]),
-        _ => unimplemented!(),
-    };
+    let attrs = get_attrs(&input.attrs);
 
-    (parser_ident, generics, attr)
+    (parser_ident, generics, attrs)
+}
+
+fn get_attrs(attrs: &[Attribute]) -> Result<(GrammarData, GrammarTypes), proc_macro2::TokenStream> {
+    let mut grammar_data = GrammarData::None;
+    let mut types = GrammarTypes::default();
+
+    for attr in attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("mplg") || attr.path.is_ident("mplg_inline"))
+    {
+        match attr.parse_meta() {
+            Ok(Meta::List(list)) => {
+                for nested in list.nested {
+                    set_grammar_type(&mut types, &nested)?;
+                }
+            }
+            _ => grammar_data = get_grammar_data(attr)?,
+        }
+    }
+
+    Ok((grammar_data, types))
+}
+
+fn set_grammar_type(
+    types: &mut GrammarTypes,
+    nested: &NestedMeta,
+) -> Result<(), proc_macro2::TokenStream> {
+    if let NestedMeta::Meta(Meta::NameValue(name_value)) = nested {
+        if let Lit::Str(lit_str) = &name_value.lit {
+            let ty = if name_value.path.is_ident("input") {
+                &mut types.input
+            } else if name_value.path.is_ident("terminal") {
+                &mut types.terminal
+            } else {
+                return Err(syn::Error::new_spanned(
+                    &name_value.path,
+                    "expected `input` or `terminal`",
+                )
+                .to_compile_error());
+            };
+            *ty = lit_str.parse().map_err(|e| e.to_compile_error())?;
+
+            return Ok(());
+        }
+    }
+
+    Err(
+        syn::Error::new_spanned(nested, "expected `input = \"...\"` or `terminal = \"...\"`")
+            .to_compile_error(),
+    )
 }
 
 fn get_grammar_data(attr: &Attribute) -> Result<GrammarData, proc_macro2::TokenStream> {
     if let Ok(Meta::NameValue(name_value)) = attr.parse_meta() {
         match name_value.lit {
-            Lit::Str(lit_str) => {
-                if name_value.path.is_ident("mplg") {
-                    let attr = lit_str.value();
-                    let root = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".into());
-                    let full_path = Path::new(&root).join(&attr);
-                    let data = match read_file(&full_path) {
-                        Ok(data) => data,
-                        Err(e) => panic!("{} ({:#?})", e, full_path),
-                    };
+            Lit::Str(lit_str) if name_value.path.is_ident("mplg") => {
+                let attr = lit_str.value();
+                let root = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".into());
+                let full_path = Path::new(&root).join(&attr);
+                let data = match read_file(&full_path) {
+                    Ok(data) => data,
+                    Err(e) => {
+                        let message = format!("{} ({:#?})", e, full_path);
+                        return Err(syn::Error::new_spanned(lit_str, message).to_compile_error());
+                    }
+                };
 
-                    return Ok(GrammarData::Mplg(data));
-                } else {
-                    // unimplemented!()
-                }
+                return Ok(GrammarData::Mplg {
+                    data,
+                    source: attr,
+                    span: lit_str.span(),
+                });
+            }
+            Lit::Str(lit_str) if name_value.path.is_ident("mplg_inline") => {
+                return Ok(GrammarData::Mplg {
+                    data: lit_str.value().into_bytes(),
+                    source: "mplg_inline".to_string(),
+                    span: lit_str.span(),
+                });
             }
             _ => {
                 // unimplemented!()
@@ -97,7 +212,10 @@ fn get_grammar_data(attr: &Attribute) -> Result<GrammarData, proc_macro2::TokenS
         }
     }
 
-    Err(syn::Error::new_spanned(attr, "expected `mplg = \"...\"`").to_compile_error())
+    Err(
+        syn::Error::new_spanned(attr, "expected `mplg = \"...\"` or `mplg_inline = \"...\"`")
+            .to_compile_error(),
+    )
 }
 
 fn read_file<P: AsRef<Path>>(path: P) -> std::io::Result<Vec<u8>> {
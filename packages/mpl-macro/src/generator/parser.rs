@@ -1,19 +1,22 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::Ident;
+use syn::{Ident, Type};
 
 pub fn generate_parser(
     parser_ident: &Ident,
     rules_ident: &Ident,
     variable_ident: &Ident,
+    input: &Type,
+    terminal: &Type,
 ) -> TokenStream {
     quote! {
-        impl<'i, P, L, O>
-            ::mpl::parser::Parser<'i, [u8], ::mpl::symbols::U8SliceTerminal<'i>, #variable_ident, ::mpl::span::StartAndLenSpan<P, L>, P, #rules_ident, O> for #parser_ident
+        impl<'a, P, L, O>
+            ::mpl::parser::Parser<'a, #input, #terminal, #variable_ident, ::mpl::span::StartAndLenSpan<P, L>, P, #rules_ident, O> for #parser_ident
         where
-            P: ::mpl::span::Start<[u8], L>,
-            L: ::mpl::span::Len<[u8], P>,
-            O: ::mpl::output::Output<'i, [u8], #variable_ident, ::mpl::span::StartAndLenSpan<P, L>>,
+            #terminal: ::mpl::symbols::Terminal<'a, #input, #variable_ident, ::mpl::span::StartAndLenSpan<P, L>, P, O>,
+            P: ::mpl::span::Start<#input, L>,
+            L: ::mpl::span::Len<#input, P>,
+            O: ::mpl::output::Output<'a, #input, #variable_ident, ::mpl::span::StartAndLenSpan<P, L>>,
         {
         }
     }
@@ -1,41 +1,70 @@
-use crate::mplg::MplgOutput;
+use super::diagnostic::Location;
+use crate::mplg::{MplgOutput, OriginalSymbol};
 use mpl::symbols::{Metasymbol, TerminalSymbol, E};
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 use std::str::FromStr;
-use syn::Ident;
+use syn::{Ident, Type};
 
-pub fn generate_e<'a>(e: &E<&'a str, &'a str>, variable_ident: &Ident) -> TokenStream {
-    match e {
+/// Returns the expression of `e`, or the Rust code in `e` which is not valid tokens.
+pub fn generate_e<'a>(
+    e: &E<OriginalSymbol<'a>, &'a str>,
+    variable_ident: &Ident,
+    terminal: &Type,
+) -> Result<TokenStream, &'a str> {
+    let tokens = |s: &'a str| TokenStream::from_str(s).map_err(|_| s);
+
+    Ok(match e {
         E::T(t) => match t {
             TerminalSymbol::Metasymbol(m) => match m {
                 Metasymbol::Failure => quote! {
-                    ::mpl::symbols::E::<::mpl::symbols::U8SliceTerminal, #variable_ident>::T(::mpl::symbols::TerminalSymbol::Metasymbol(
+                    ::mpl::symbols::E::<#terminal, #variable_ident>::T(::mpl::symbols::TerminalSymbol::Metasymbol(
                         ::mpl::symbols::Metasymbol::Failure
                     ))
                 },
                 Metasymbol::Empty => quote! {
-                    ::mpl::symbols::E::<::mpl::symbols::U8SliceTerminal, #variable_ident>::T(::mpl::symbols::TerminalSymbol::Metasymbol(
+                    ::mpl::symbols::E::<#terminal, #variable_ident>::T(::mpl::symbols::TerminalSymbol::Metasymbol(
                         ::mpl::symbols::Metasymbol::Empty
                     ))
                 },
                 Metasymbol::All => quote! {
-                    ::mpl::symbols::E::<::mpl::symbols::U8SliceTerminal, #variable_ident>::T(::mpl::symbols::TerminalSymbol::Metasymbol(
+                    ::mpl::symbols::E::<#terminal, #variable_ident>::T(::mpl::symbols::TerminalSymbol::Metasymbol(
                         ::mpl::symbols::Metasymbol::All
                     ))
                 },
                 Metasymbol::Any(n) => quote! {
-                    ::mpl::symbols::E::<::mpl::symbols::U8SliceTerminal, #variable_ident>::T(::mpl::symbols::TerminalSymbol::Metasymbol(
+                    ::mpl::symbols::E::<#terminal, #variable_ident>::T(::mpl::symbols::TerminalSymbol::Metasymbol(
                         ::mpl::symbols::Metasymbol::Any(#n)
                     ))
                 },
-                _ => unreachable!(),
+                Metasymbol::Omit => quote! {
+                    ::mpl::symbols::E::<#terminal, #variable_ident>::T(::mpl::symbols::TerminalSymbol::Metasymbol(
+                        ::mpl::symbols::Metasymbol::Omit
+                    ))
+                },
+                Metasymbol::And => quote! {
+                    ::mpl::symbols::E::<#terminal, #variable_ident>::T(::mpl::symbols::TerminalSymbol::Metasymbol(
+                        ::mpl::symbols::Metasymbol::And
+                    ))
+                },
+                Metasymbol::Not => quote! {
+                    ::mpl::symbols::E::<#terminal, #variable_ident>::T(::mpl::symbols::TerminalSymbol::Metasymbol(
+                        ::mpl::symbols::Metasymbol::Not
+                    ))
+                },
             },
             TerminalSymbol::Original(o) => {
-                let o = TokenStream::from_str(o).unwrap();
+                let o = match o {
+                    OriginalSymbol::Expr(expr) => tokens(expr)?,
+                    OriginalSymbol::CharRange(start, end) => {
+                        let start = tokens(start)?;
+                        let end = tokens(end)?;
+                        quote! { CharRange(#start, #end) }
+                    }
+                };
                 quote! {
-                    ::mpl::symbols::E::<::mpl::symbols::U8SliceTerminal, #variable_ident>::T(::mpl::symbols::TerminalSymbol::Original(
-                        ::mpl::symbols::U8SliceTerminal::#o
+                    ::mpl::symbols::E::<#terminal, #variable_ident>::T(::mpl::symbols::TerminalSymbol::Original(
+                        <#terminal>::#o
                     ))
                 }
             }
@@ -43,30 +72,46 @@ pub fn generate_e<'a>(e: &E<&'a str, &'a str>, variable_ident: &Ident) -> TokenS
         E::V(v) => {
             let ident = format_ident!("{}", v);
             quote! {
-                ::mpl::symbols::E::<::mpl::symbols::U8SliceTerminal, #variable_ident>::V(#variable_ident::#ident)
+                ::mpl::symbols::E::<#terminal, #variable_ident>::V(#variable_ident::#ident)
             }
         }
-    }
+    })
 }
 
+/// Returns messages of all Rust code which is not valid tokens if any.
 pub fn generate_rules(
     rules_ident: &Ident,
     variable_ident: &Ident,
+    terminal: &Type,
+    source: &str,
+    data: &[u8],
     lines: &[MplgOutput],
-) -> TokenStream {
+) -> Result<TokenStream, Vec<String>> {
+    let mut messages = Vec::new();
+    let mut generate_e = |e, span| {
+        generate_e(e, variable_ident, terminal).unwrap_or_else(|code| {
+            messages.push(format!(
+                "invalid tokens `{}` at {}",
+                code,
+                Location::of_span(source, data, span)
+            ));
+            TokenStream::new()
+        })
+    };
+
     let const_rules = lines
         .iter()
-        .filter(|line| matches!(line, &&MplgOutput::Rule(_)))
+        .filter(|line| matches!(line, &&MplgOutput::Rule(..)))
         .map(|line| match line {
-            MplgOutput::Rule(rule) => {
+            MplgOutput::Rule(rule, spans) => {
                 let variable = format_ident!("{}", rule.value);
                 let const_ident = format_ident!("{}_RULE", variable);
-                let fl = generate_e(&rule.equal.first.lhs, variable_ident);
-                let fr = generate_e(&rule.equal.first.rhs, variable_ident);
-                let s = generate_e(&rule.equal.second.0, variable_ident);
+                let fl = generate_e(&rule.equal.first.lhs, &spans.first_lhs);
+                let fr = generate_e(&rule.equal.first.rhs, &spans.first_rhs);
+                let s = generate_e(&rule.equal.second.0, &spans.second);
 
                 quote! {
-                    pub const #const_ident: ::mpl::rules::RightRule<::mpl::symbols::U8SliceTerminal<'a>, #variable_ident> = ::mpl::rules::RightRule {
+                    pub const #const_ident: ::mpl::rules::RightRule<#terminal, #variable_ident> = ::mpl::rules::RightRule {
                         first: ::mpl::choices::First {
                             lhs: #fl,
                             rhs: #fr,
@@ -76,43 +121,76 @@ pub fn generate_rules(
                 }
             }
             _ => unreachable!(),
-        });
+        })
+        .collect::<Vec<_>>();
+    if !messages.is_empty() {
+        return Err(messages);
+    }
 
-    // TODO BUG
-    // let match_rules = lines
-    //     .iter()
-    //     .filter(|line| matches!(line, &&MplgOutput::Rule(_)))
-    //     .map(|line| match line {
-    //         MplgOutput::Rule(rule) => {
-    //             dbg!(&rule);
-    //             let variable = format_ident!("{}", rule.value);
-    //             let const_ident = format_ident!("{}_RULE", variable);
-    //             quote! {
-    //                 #variable => {
-    //                     &Self::#const_ident
-    //                 }
-    //             }
-    //         }
-    //         _ => unreachable!(),
-    //     });
+    let match_rules = lines
+        .iter()
+        .filter(|line| matches!(line, &&MplgOutput::Rule(..)))
+        .map(|line| match line {
+            MplgOutput::Rule(rule, _) => {
+                let variable = format_ident!("{}", rule.value);
+                let const_ident = format_ident!("{}_RULE", variable);
+                quote! {
+                    #variable_ident::#variable => &Self::#const_ident,
+                }
+            }
+            _ => unreachable!(),
+        });
 
-    quote! {
+    Ok(quote! {
         pub struct #rules_ident;
 
         impl<'a> #rules_ident {
             #(#const_rules)*
         }
 
-        // TODO BUG
-        // impl<'a> ::mpl::rules::Rules<::mpl::symbols::U8SliceTerminal<'a>, #variable_ident> for #rules_ident {
-        //     fn get(&self, variable: &#variable_ident) -> Option<&::mpl::rules::RightRule<::mpl::symbols::U8SliceTerminal<'a>, #variable_ident>> {
-        //         eprintln!("Get Variable: {:?}", variable);
-        //         let a = Some(match variable {
-        //             #(#match_rules)*
-        //         });
-        //         eprintln!("{:#?}", a);
-        //         a
-        //     }
-        // }
+        impl<'a> ::mpl::rules::Rules<#terminal, #variable_ident> for #rules_ident {
+            fn get(&self, variable: &#variable_ident) -> Option<&::mpl::rules::RightRule<#terminal, #variable_ident>> {
+                Some(match *variable {
+                    #(#match_rules)*
+                })
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::parse_error_message;
+    use crate::mplg::parse_mplg;
+    use syn::parse_quote;
+
+    #[test]
+    fn invalid_tokens() {
+        let data = br#"A = { Char('a') } () / f
+B = () { Str("\q") } / { Str("\u{110000}") }
+"#;
+        let lines = parse_mplg(data)
+            .map_err(|e| parse_error_message("my.mplg", data, e))
+            .unwrap()
+            .into_original()
+            .unwrap()
+            .into_lines();
+        let messages = generate_rules(
+            &format_ident!("MyRules"),
+            &format_ident!("MyVariable"),
+            &parse_quote!(::mpl::symbols::StrTerminal<'a>),
+            "my.mplg",
+            data,
+            &lines,
+        )
+        .unwrap_err();
+        assert_eq!(
+            messages,
+            vec![
+                r#"invalid tokens `Str("\q")` at my.mplg:2:8"#.to_string(),
+                r#"invalid tokens `Str("\u{110000}")` at my.mplg:2:24"#.to_string(),
+            ]
+        );
     }
 }
@@ -6,9 +6,9 @@ use syn::Ident;
 pub fn generate_variable(ident: &Ident, lines: &[MplgOutput]) -> TokenStream {
     let variables = lines
         .iter()
-        .filter(|line| matches!(line, MplgOutput::Rule(_)))
+        .filter(|line| matches!(line, MplgOutput::Rule(..)))
         .map(|line| match line {
-            MplgOutput::Rule(rule) => {
+            MplgOutput::Rule(rule, _) => {
                 let variable = format_ident!("{}", rule.value);
                 quote!(#variable)
             }
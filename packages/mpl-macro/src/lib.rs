@@ -7,8 +7,27 @@ use proc_macro::TokenStream;
 /// let ident = parser_ident.replace("Parser", "");
 /// `{ident}Variable` enum,
 /// `{ident}Rules` const {variable_i_ident}_RULE for each rule,
+/// impl Rules for `{ident}Rules`,
 /// and impl Parser for `{parser_ident}`.
 ///
+/// An invalid grammar is a compile error with its line and column,
+/// like `expected " / " at my.mplg:3:10`.
+/// So is each variable without exactly one rule,
+/// like ``undefined variable `B` at my.mplg:1:5``,
+/// and Rust code in `{ }` which is not valid tokens.
+/// Rules are also analyzed with the first variable as the start variable,
+/// so left recursion and variables not reachable from it are compile errors.
+///
+/// ```compile_fail
+/// use mpl_macro::Parse;
+///
+/// #[derive(Parse)]
+/// #[mplg_inline = "
+/// Expr = Expr { Char('+') } / { Char('1') }
+/// "]
+/// pub struct AddParser;
+/// ```
+///
 /// # Examples
 ///
 /// ``` ignore
@@ -18,7 +37,45 @@ use proc_macro::TokenStream;
 /// #[mplg = "{your path}/my.mplg"]
 /// pub struct MyParser;
 /// ```
-#[proc_macro_derive(Parse, attributes(mplg))]
+///
+/// `#[mplg_inline = "..."]` gives the grammar directly.
+///
+/// ```
+/// use mpl::parser::Parser;
+/// use mpl::span::StartAndLenSpan;
+/// use mpl::trees::AST;
+/// use mpl_macro::Parse;
+///
+/// #[derive(Parse)]
+/// #[mplg_inline = "
+/// Open = { Char('(') } Parentheses / ()
+/// Parentheses = Open Close / f
+/// Close = { Char(')') } Open / f
+/// "]
+/// pub struct ParenParser;
+///
+/// let input = b"(()())";
+/// let all_of_the_span = StartAndLenSpan::<u32, u16>::from_start_len(0, input.len() as u16);
+/// let result: Result<AST<ParenVariable, _, ()>, _> =
+///     ParenParser.parse(input, &ParenRules, &ParenVariable::Open, &all_of_the_span);
+///
+/// assert!(result.is_ok());
+/// ```
+///
+/// Input is `[u8]` and terminal symbols are `U8SliceTerminal` by default.
+/// `#[mplg(input = "...", terminal = "...")]` changes them,
+/// where `'a` is the lifetime of input and terminal symbols.
+/// The parser is implemented for the positions and outputs for which the terminal symbols implement `Terminal`.
+///
+/// ``` ignore
+/// use mpl_macro::Parse;
+///
+/// #[derive(Parse)]
+/// #[mplg = "{your path}/my.mplg"]
+/// #[mplg(input = "str", terminal = "mpl::symbols::StrTerminal<'a>")]
+/// pub struct MyParser;
+/// ```
+#[proc_macro_derive(Parse, attributes(mplg, mplg_inline))]
 pub fn derive_parse(input: TokenStream) -> TokenStream {
     generator::derive_parser(input.into()).into()
 }
@@ -1,7 +1,9 @@
+use mpl::error::ParseError;
 use mpl::span::StartAndLenSpan;
+use mpl::symbols::U8SliceTerminal;
 use mpl::trees::AST;
 
-pub use self::output::MplgOutput;
+pub use self::output::{MplgOutput, OriginalSymbol};
 pub use self::parser::parse_mplg;
 pub use self::rules::MplgRules;
 pub use self::variable::MplgVariable;
@@ -12,3 +14,5 @@ mod rules;
 mod variable;
 
 pub type MplgAST<'i> = AST<MplgVariable, StartAndLenSpan<u32, u32>, MplgOutput<'i>>;
+pub type MplgParseError<'i> =
+    ParseError<U8SliceTerminal<'i>, MplgVariable, StartAndLenSpan<u32, u32>, u32, MplgOutput<'i>>;
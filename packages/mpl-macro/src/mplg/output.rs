@@ -6,12 +6,34 @@ use mpl::span::{Span, StartAndLenSpan};
 use mpl::symbols::{Metasymbol, TerminalSymbol, E};
 use mpl::trees::{Node, AST, CST};
 
+/// Original symbol in `{ }`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OriginalSymbol<'a> {
+    /// Expression of the terminal type such as `Char('a')`.
+    Expr(&'a str),
+    /// `'a'..='z'`, which is `CharRange('a', 'z')`.
+    CharRange(&'a str, &'a str),
+}
+
+/// Spans of the symbols of a rule `A = B C / D` in the grammar.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RuleSpans {
+    /// `A`
+    pub variable: StartAndLenSpan<u32, u32>,
+    /// `B`
+    pub first_lhs: StartAndLenSpan<u32, u32>,
+    /// `C`
+    pub first_rhs: StartAndLenSpan<u32, u32>,
+    /// `D`
+    pub second: StartAndLenSpan<u32, u32>,
+}
+
 #[derive(Clone, Debug)]
 pub enum MplgOutput<'a> {
     Lines(Vec<MplgOutput<'a>>),
-    Rule(Rule<&'a str, &'a str>),
+    Rule(Rule<OriginalSymbol<'a>, &'a str>, RuleSpans),
     Str(&'a str),
-    E(E<&'a str, &'a str>),
+    E(E<OriginalSymbol<'a>, &'a str>),
 }
 
 impl<'a> MplgOutput<'a> {
@@ -29,7 +51,7 @@ impl<'a> MplgOutput<'a> {
         }
     }
 
-    fn into_e(self) -> E<&'a str, &'a str> {
+    fn into_e(self) -> E<OriginalSymbol<'a>, &'a str> {
         match self {
             MplgOutput::E(e) => e,
             _ => panic!("expect E"),
@@ -74,7 +96,7 @@ impl<'i> Output<'i, [u8], MplgVariable, StartAndLenSpan<u32, u32>> for MplgOutpu
                 let span = cst.span;
                 let first = cst.node.equal.into_first().unwrap();
                 let line1 = first.lhs.node.into_internal().unwrap();
-                match *line1.equal {
+                match line1.equal.into_choice() {
                     Choice::First(first) => {
                         let mut line_comment = first.lhs;
                         line_comment.span = span;
@@ -103,20 +125,33 @@ impl<'i> Output<'i, [u8], MplgVariable, StartAndLenSpan<u32, u32>> for MplgOutpu
             MplgVariable::Rule => {
                 let span = cst.span;
                 let first = cst.node.equal.into_first().unwrap();
+                let variable_span = first.lhs.span.clone();
                 let variable = first.lhs.into_original().unwrap().into_str();
                 let rule2 = first.rhs.into_first().unwrap().rhs.into_first().unwrap();
                 // First
                 // lhs
+                let fl_span = rule2.lhs.span.clone();
                 let fl = rule2.lhs.into_original().unwrap().into_e();
                 let rule4 = rule2.rhs.into_first().unwrap().rhs.into_first().unwrap();
                 // rhs
+                let fr_span = rule4.lhs.span.clone();
                 let fr = rule4.lhs.into_original().unwrap().into_e();
                 let rule6 = rule4.rhs.into_first().unwrap().rhs.into_first().unwrap();
                 // Second
+                let s_span = rule6.lhs.span.clone();
                 let s = rule6.lhs.into_original().unwrap().into_e();
 
                 let rule = Rule::new(variable, RightRule::new(First::new(fl, fr), Second::new(s)));
-                AST::from_leaf(TerminalSymbol::from_original(MplgOutput::Rule(rule)), span)
+                let spans = RuleSpans {
+                    variable: variable_span,
+                    first_lhs: fl_span,
+                    first_rhs: fr_span,
+                    second: s_span,
+                };
+                AST::from_leaf(
+                    TerminalSymbol::from_original(MplgOutput::Rule(rule, spans)),
+                    span,
+                )
             }
             // E
             MplgVariable::E => {
@@ -166,6 +201,18 @@ impl<'i> Output<'i, [u8], MplgVariable, StartAndLenSpan<u32, u32>> for MplgOutpu
                 TerminalSymbol::from_original(MplgOutput::E(Metasymbol::All.into())),
                 cst.span,
             ),
+            MplgVariable::OmitLiteral => AST::from_leaf(
+                TerminalSymbol::from_original(MplgOutput::E(Metasymbol::Omit.into())),
+                cst.span,
+            ),
+            MplgVariable::AndLiteral => AST::from_leaf(
+                TerminalSymbol::from_original(MplgOutput::E(Metasymbol::And.into())),
+                cst.span,
+            ),
+            MplgVariable::NotLiteral => AST::from_leaf(
+                TerminalSymbol::from_original(MplgOutput::E(Metasymbol::Not.into())),
+                cst.span,
+            ),
             MplgVariable::MetasymbolLiteral => {
                 let span = cst.span;
                 let mut choice = cst.node.equal;
@@ -176,25 +223,61 @@ impl<'i> Output<'i, [u8], MplgVariable, StartAndLenSpan<u32, u32>> for MplgOutpu
                             return AST::from_leaf(TerminalSymbol::from_original(meta), span);
                         }
                         Choice::Second(second) => {
-                            choice = *second.0.node.into_internal().expect("internal").equal;
+                            choice = second
+                                .0
+                                .node
+                                .into_internal()
+                                .expect("internal")
+                                .equal
+                                .into_choice();
                         }
                     }
                 }
             }
             // Original symbol
             MplgVariable::OriginalSymbolExpr => {
-                let lo = cst.span.start as usize;
-                let hi = cst.span.hi(input) as usize;
+                let span = cst.span.clone();
+                let lo = span.start as usize;
+                let hi = span.hi(input) as usize;
                 let s = std::str::from_utf8(&input[lo + 2..hi - 2]).expect("str");
 
+                let expr_without_block = cst
+                    .into_first()
+                    .expect("first")
+                    .rhs
+                    .into_first()
+                    .expect("first")
+                    .lhs;
+                let e = match expr_without_block.into_first() {
+                    // RangeExpr
+                    Some(first) => first.lhs.into_original().expect("RangeExpr").into_e(),
+                    None => TerminalSymbol::Original(OriginalSymbol::Expr(s)).into(),
+                };
+
+                AST::from_leaf(TerminalSymbol::from_original(MplgOutput::E(e)), span)
+            }
+            // Range
+            MplgVariable::RangeExpr => {
+                let span = cst.span.clone();
+                let first = cst.into_first().expect("first");
+                let start = str_of(input, &first.lhs.span);
+                let end = str_of(input, &first.rhs.into_first().expect("first").rhs.span);
+
                 AST::from_leaf(
                     TerminalSymbol::from_original(MplgOutput::E(
-                        TerminalSymbol::Original(s).into(),
+                        TerminalSymbol::Original(OriginalSymbol::CharRange(start, end)).into(),
                     )),
-                    cst.span,
+                    span,
                 )
             }
             _ => AST::from_cst(cst),
         }
     }
 }
+
+/// Returns the text of `span` in `input`.
+fn str_of<'i>(input: &'i [u8], span: &StartAndLenSpan<u32, u32>) -> &'i str {
+    let lo = span.start as usize;
+    let hi = span.hi(input) as usize;
+    std::str::from_utf8(&input[lo..hi]).expect("str")
+}
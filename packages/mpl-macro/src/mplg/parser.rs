@@ -1,4 +1,4 @@
-use crate::mplg::{MplgAST, MplgRules, MplgVariable};
+use crate::mplg::{MplgAST, MplgParseError, MplgRules, MplgVariable};
 use mpl::output::Output;
 use mpl::parser::Parser;
 use mpl::span::{Len, Start, StartAndLenSpan};
@@ -16,7 +16,8 @@ where
 {
 }
 
-pub fn parse_mplg(input: &[u8]) -> Result<MplgAST, MplgAST> {
+#[allow(clippy::result_large_err)]
+pub fn parse_mplg(input: &[u8]) -> Result<MplgAST<'_>, MplgParseError<'_>> {
     let parser = MplgParser;
     let all_of_the_span = StartAndLenSpan::<u32, u32>::from_start_len(0, input.len() as u32);
     let rules = &MplgRules;
@@ -61,6 +62,9 @@ mod tests {
             ("A = f f / f\n", ParseResult::Ok),
             ("A = ? ?? / ???\n", ParseResult::Ok),
             ("A = * * / *\n", ParseResult::Ok),
+            ("A = _ B / _\n", ParseResult::Ok),
+            ("A = & B / !\n", ParseResult::Ok),
+            ("A = ! { 'a' } / &\n", ParseResult::Ok),
             // Chars
             ("A = { 'b' } { 'c' } / { 'd' }\n", ParseResult::Ok),
             // ("A = { '\'' } { '\'' } / { '\'' }\n", ParseResult::Ok),
@@ -79,6 +83,15 @@ mod tests {
                 "A = { Str(\"b\") } { Null(1) } / { A(2) }\n",
                 ParseResult::Ok,
             ),
+            // Char classes
+            (
+                "A = { 'a'..='z' } { CharRange('0', '9') } / { XidStart }\n",
+                ParseResult::Ok,
+            ),
+            ("A = { CharSet(\"+-\") } () / f\n", ParseResult::Ok),
+            // Byte classes
+            ("A = { ByteRange(48, 57) } () / f\n", ParseResult::Ok),
+            ("A = { IStr(\"select\") } () / f\n", ParseResult::Ok),
             // Mplg
             (
                 "// Mplg = Line Mplg / ()\nMplg = Line Mplg / ()\r\n\n",
@@ -92,6 +105,8 @@ mod tests {
             ("A = b c / d\n", ParseResult::Err),
             ("() = B C / D\n", ParseResult::Err),
             ("A = { 'bb' } { 'c' } / { 'd' }\n", ParseResult::Err),
+            ("A = { 'a'.. } () / f\n", ParseResult::Err),
+            ("A = { 'a'..='z'..='Z' } () / f\n", ParseResult::Err),
         ];
         for input in inputs {
             let result = parse_mplg(input.0.as_bytes());
@@ -101,4 +116,43 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn original_symbols() {
+        use crate::mplg::{MplgOutput, OriginalSymbol};
+        use mpl::symbols::{TerminalSymbol, E};
+
+        let input = b"A = { 'a'..='z' } { CharRange('0', '9') } / { XidStart }\n";
+        let lines = parse_mplg(input)
+            .unwrap()
+            .into_original()
+            .unwrap()
+            .into_lines();
+        let (rule, spans) = match &lines[0] {
+            MplgOutput::Rule(rule, spans) => (rule, spans),
+            _ => panic!("expect rule"),
+        };
+
+        assert_eq!(
+            rule.equal.first.lhs,
+            E::T(TerminalSymbol::Original(OriginalSymbol::CharRange(
+                "'a'", "'z'"
+            )))
+        );
+        assert_eq!(
+            rule.equal.first.rhs,
+            E::T(TerminalSymbol::Original(OriginalSymbol::Expr(
+                "CharRange('0', '9')"
+            )))
+        );
+        assert_eq!(
+            rule.equal.second.0,
+            E::T(TerminalSymbol::Original(OriginalSymbol::Expr("XidStart")))
+        );
+
+        assert_eq!(spans.variable, StartAndLenSpan::from_start_len(0, 1));
+        assert_eq!(spans.first_lhs, StartAndLenSpan::from_start_len(4, 13));
+        assert_eq!(spans.first_rhs, StartAndLenSpan::from_start_len(18, 23));
+        assert_eq!(spans.second, StartAndLenSpan::from_start_len(44, 12));
+    }
 }
@@ -63,18 +63,29 @@ impl<'a> MplgRules {
     mplg_rule!(
         EXPR_WITHOUT_BLOCK_RULE,
         ExprWithoutBlock,
-        LiteralExpr,
+        RangeExpr,
         (),
         ExprWithoutBlock1
     );
     mplg_rule!(
         EXPR_WITHOUT_BLOCK1_RULE,
         ExprWithoutBlock1,
+        LiteralExpr,
+        (),
+        ExprWithoutBlock2
+    );
+    mplg_rule!(
+        EXPR_WITHOUT_BLOCK2_RULE,
+        ExprWithoutBlock2,
         StructExpr,
         (),
         f
     );
 
+    // Range
+    mplg_rule!(RANGE_EXPR_RULE, RangeExpr, CharLiteral, RangeExpr1, f);
+    mplg_rule!(RANGE_EXPR1_RULE, RangeExpr1, { Str("..=") }, CharLiteral, f);
+
     // Struct
     mplg_rule!(
         STRUCT_EXPR_RULE,
@@ -114,7 +125,20 @@ impl<'a> MplgRules {
         { Char(')') },
         f
     );
-    mplg_rule!(ZERO_OR_MORE_EXPR_RULE, ZeroOrMoreExpr, Expr, (), f);
+    mplg_rule!(
+        ZERO_OR_MORE_EXPR_RULE,
+        ZeroOrMoreExpr,
+        Expr,
+        ZeroOrMoreExpr1,
+        f
+    );
+    mplg_rule!(
+        ZERO_OR_MORE_EXPR1_RULE,
+        ZeroOrMoreExpr1,
+        { Str(", ") },
+        ZeroOrMoreExpr,
+        ()
+    );
 
     mplg_rule!(STRUCT_EXPR_UNIT_RULE, StructExprUnit, PathInExpr, (), f);
 
@@ -204,6 +228,27 @@ impl<'a> MplgRules {
         MetasymbolLiteral3,
         AllLiteral,
         (),
+        MetasymbolLiteral4
+    );
+    mplg_rule!(
+        METASYMBOL_LITERAL4_RULE,
+        MetasymbolLiteral4,
+        OmitLiteral,
+        (),
+        MetasymbolLiteral5
+    );
+    mplg_rule!(
+        METASYMBOL_LITERAL5_RULE,
+        MetasymbolLiteral5,
+        AndLiteral,
+        (),
+        MetasymbolLiteral6
+    );
+    mplg_rule!(
+        METASYMBOL_LITERAL6_RULE,
+        MetasymbolLiteral6,
+        NotLiteral,
+        (),
         f
     );
     mplg_rule!(EMPTY_RULE, EmptyLiteral, { Str("()") }, (), f);
@@ -223,6 +268,9 @@ impl<'a> MplgRules {
         ()
     );
     mplg_rule!(ALL_LITERAL_RULE, AllLiteral, { Char('*') }, (), f);
+    mplg_rule!(OMIT_LITERAL_RULE, OmitLiteral, { Char('_') }, (), f);
+    mplg_rule!(AND_LITERAL_RULE, AndLiteral, { Char('&') }, (), f);
+    mplg_rule!(NOT_LITERAL_RULE, NotLiteral, { Char('!') }, (), f);
 
     // Original symbol
     mplg_rule!(
@@ -262,7 +310,7 @@ impl<'a> MplgRules {
         InnerCharLiteral1,
         f
     );
-    mplg_rule!(NOT_CHAR_LETTER_RULE, NotCharLetter, { Char('\'') }, *, ());
+    mplg_rule!(NOT_CHAR_LETTER_RULE, NotCharLetter, !, { Char('\'') }, f);
     mplg_rule!(INNER_CHAR_LITERAL1_RULE, InnerCharLiteral1, QuoteEscape, (), ?);
 
     // String
@@ -295,7 +343,7 @@ impl<'a> MplgRules {
         InnerStringLiteralLetter1,
         f
     );
-    mplg_rule!(NOT_STRING_LETTER_RULE, NotStringLetter, { Char('"') }, *, ());
+    mplg_rule!(NOT_STRING_LETTER_RULE, NotStringLetter, !, { Char('"') }, f);
     mplg_rule!(INNER_STRING_LITERAL_LETTER1_RULE, InnerStringLiteralLetter1, QuoteEscape, (), ?);
 
     // Integer
@@ -349,243 +397,9 @@ impl<'a> MplgRules {
     // Letters
     mplg_rule!(ALPHABET_RULE, Alphabet, Lowercase, (), Uppercase);
     // Lowercase
-    mplg_rule!(LOWERCASE_A_TO_F_RULE, LowercaseAToF, LowercaseAToF1, (), f);
-    mplg_rule!(
-        LOWERCASE_A_TO_F1_RULE,
-        LowercaseAToF1,
-        { Char('a') },
-        (),
-        LowercaseAToF2
-    );
-    mplg_rule!(
-        LOWERCASE_A_TO_F2_RULE,
-        LowercaseAToF2,
-        { Char('b') },
-        (),
-        LowercaseAToF3
-    );
-    mplg_rule!(
-        LOWERCASE_A_TO_F3_RULE,
-        LowercaseAToF3,
-        { Char('c') },
-        (),
-        LowercaseAToF4
-    );
-    mplg_rule!(
-        LOWERCASE_A_TO_F4_RULE,
-        LowercaseAToF4,
-        { Char('d') },
-        (),
-        LowercaseAToF5
-    );
-    mplg_rule!(
-        LOWERCASE_A_TO_F5_RULE,
-        LowercaseAToF5,
-        { Char('e') },
-        (),
-        LowercaseAToF6
-    );
-    mplg_rule!(LOWERCASE_A_TO_F6_RULE, LowercaseAToF6, { Char('f') }, (), f);
-    mplg_rule!(LOWERCASE_RULE, Lowercase, LowercaseAToF, (), Lowercase1);
-    mplg_rule!(LOWERCASE1_RULE, Lowercase1, { Char('g') }, (), Lowercase2);
-    mplg_rule!(LOWERCASE2_RULE, Lowercase2, { Char('h') }, (), Lowercase3);
-    mplg_rule!(LOWERCASE3_RULE, Lowercase3, { Char('i') }, (), Lowercase4);
-    mplg_rule!(LOWERCASE4_RULE, Lowercase4, { Char('j') }, (), Lowercase5);
-    mplg_rule!(LOWERCASE5_RULE, Lowercase5, { Char('k') }, (), Lowercase6);
-    mplg_rule!(LOWERCASE6_RULE, Lowercase6, { Char('l') }, (), Lowercase7);
-    mplg_rule!(LOWERCASE7_RULE, Lowercase7, { Char('m') }, (), Lowercase8);
-    mplg_rule!(LOWERCASE8_RULE, Lowercase8, { Char('n') }, (), Lowercase9);
-    mplg_rule!(LOWERCASE9_RULE, Lowercase9, { Char('o') }, (), Lowercase10);
-    mplg_rule!(
-        LOWERCASE10_RULE,
-        Lowercase10,
-        { Char('p') },
-        (),
-        Lowercase11
-    );
-    mplg_rule!(
-        LOWERCASE11_RULE,
-        Lowercase11,
-        { Char('q') },
-        (),
-        Lowercase12
-    );
-    mplg_rule!(
-        LOWERCASE12_RULE,
-        Lowercase12,
-        { Char('r') },
-        (),
-        Lowercase13
-    );
-    mplg_rule!(
-        LOWERCASE13_RULE,
-        Lowercase13,
-        { Char('s') },
-        (),
-        Lowercase14
-    );
-    mplg_rule!(
-        LOWERCASE14_RULE,
-        Lowercase14,
-        { Char('t') },
-        (),
-        Lowercase15
-    );
-    mplg_rule!(
-        LOWERCASE15_RULE,
-        Lowercase15,
-        { Char('u') },
-        (),
-        Lowercase16
-    );
-    mplg_rule!(
-        LOWERCASE16_RULE,
-        Lowercase16,
-        { Char('v') },
-        (),
-        Lowercase17
-    );
-    mplg_rule!(
-        LOWERCASE17_RULE,
-        Lowercase17,
-        { Char('w') },
-        (),
-        Lowercase18
-    );
-    mplg_rule!(
-        LOWERCASE18_RULE,
-        Lowercase18,
-        { Char('x') },
-        (),
-        Lowercase19
-    );
-    mplg_rule!(
-        LOWERCASE19_RULE,
-        Lowercase19,
-        { Char('y') },
-        (),
-        Lowercase20
-    );
-    mplg_rule!(LOWERCASE20_RULE, Lowercase20, { Char('z') }, (), f);
+    mplg_rule!(LOWERCASE_RULE, Lowercase, { CharRange('a', 'z') }, (), f);
     // Uppercase
-    mplg_rule!(UPPERCASE_A_TO_F_RULE, UppercaseAToF, UppercaseAToF1, (), f);
-    mplg_rule!(
-        UPPERCASE_A_TO_F1_RULE,
-        UppercaseAToF1,
-        { Char('A') },
-        (),
-        UppercaseAToF2
-    );
-    mplg_rule!(
-        UPPERCASE_A_TO_F2_RULE,
-        UppercaseAToF2,
-        { Char('B') },
-        (),
-        UppercaseAToF3
-    );
-    mplg_rule!(
-        UPPERCASE_A_TO_F3_RULE,
-        UppercaseAToF3,
-        { Char('C') },
-        (),
-        UppercaseAToF4
-    );
-    mplg_rule!(
-        UPPERCASE_A_TO_F4_RULE,
-        UppercaseAToF4,
-        { Char('D') },
-        (),
-        UppercaseAToF5
-    );
-    mplg_rule!(
-        UPPERCASE_A_TO_F5_RULE,
-        UppercaseAToF5,
-        { Char('E') },
-        (),
-        UppercaseAToF6
-    );
-    mplg_rule!(UPPERCASE_A_TO_F6_RULE, UppercaseAToF6, { Char('F') }, (), f);
-    mplg_rule!(UPPERCASE_RULE, Uppercase, UppercaseAToF, (), Uppercase1);
-    mplg_rule!(UPPERCASE1_RULE, Uppercase1, { Char('G') }, (), Uppercase2);
-    mplg_rule!(UPPERCASE2_RULE, Uppercase2, { Char('H') }, (), Uppercase3);
-    mplg_rule!(UPPERCASE3_RULE, Uppercase3, { Char('I') }, (), Uppercase4);
-    mplg_rule!(UPPERCASE4_RULE, Uppercase4, { Char('J') }, (), Uppercase5);
-    mplg_rule!(UPPERCASE5_RULE, Uppercase5, { Char('K') }, (), Uppercase6);
-    mplg_rule!(UPPERCASE6_RULE, Uppercase6, { Char('L') }, (), Uppercase7);
-    mplg_rule!(UPPERCASE7_RULE, Uppercase7, { Char('M') }, (), Uppercase8);
-    mplg_rule!(UPPERCASE8_RULE, Uppercase8, { Char('N') }, (), Uppercase9);
-    mplg_rule!(UPPERCASE9_RULE, Uppercase9, { Char('O') }, (), Uppercase10);
-    mplg_rule!(
-        UPPERCASE10_RULE,
-        Uppercase10,
-        { Char('P') },
-        (),
-        Uppercase11
-    );
-    mplg_rule!(
-        UPPERCASE11_RULE,
-        Uppercase11,
-        { Char('Q') },
-        (),
-        Uppercase12
-    );
-    mplg_rule!(
-        UPPERCASE12_RULE,
-        Uppercase12,
-        { Char('R') },
-        (),
-        Uppercase13
-    );
-    mplg_rule!(
-        UPPERCASE13_RULE,
-        Uppercase13,
-        { Char('S') },
-        (),
-        Uppercase14
-    );
-    mplg_rule!(
-        UPPERCASE14_RULE,
-        Uppercase14,
-        { Char('T') },
-        (),
-        Uppercase15
-    );
-    mplg_rule!(
-        UPPERCASE15_RULE,
-        Uppercase15,
-        { Char('U') },
-        (),
-        Uppercase16
-    );
-    mplg_rule!(
-        UPPERCASE16_RULE,
-        Uppercase16,
-        { Char('V') },
-        (),
-        Uppercase17
-    );
-    mplg_rule!(
-        UPPERCASE17_RULE,
-        Uppercase17,
-        { Char('W') },
-        (),
-        Uppercase18
-    );
-    mplg_rule!(
-        UPPERCASE18_RULE,
-        Uppercase18,
-        { Char('X') },
-        (),
-        Uppercase19
-    );
-    mplg_rule!(
-        UPPERCASE19_RULE,
-        Uppercase19,
-        { Char('Y') },
-        (),
-        Uppercase20
-    );
-    mplg_rule!(UPPERCASE20_RULE, Uppercase20, { Char('Z') }, (), f);
+    mplg_rule!(UPPERCASE_RULE, Uppercase, { CharRange('A', 'Z') }, (), f);
 
     mplg_rule!(QUOTE_ESCAPE_RULE, QuoteEscape, { Str("\\'") }, (), {
         Str("\\\"")
@@ -596,17 +410,7 @@ impl<'a> MplgRules {
     mplg_rule!(SPACE_RULE, Space, { Char(' ') }, (), f);
 
     // Digits
-    mplg_rule!(BIN_DIGIT_RULE, BinDigit, { Char('0') }, (), { Char('1') });
-    mplg_rule!(OCT_DIGIT_RULE, OctDigit, BinDigit, (), OctDigit1);
-    mplg_rule!(OCT_DIGIT1_RULE, OctDigit1, { Char('2') }, (), OctDigit2);
-    mplg_rule!(OCT_DIGIT2_RULE, OctDigit2, { Char('3') }, (), OctDigit3);
-    mplg_rule!(OCT_DIGIT3_RULE, OctDigit3, { Char('4') }, (), OctDigit4);
-    mplg_rule!(OCT_DIGIT4_RULE, OctDigit4, { Char('5') }, (), OctDigit5);
-    mplg_rule!(OCT_DIGIT5_RULE, OctDigit5, { Char('6') }, (), OctDigit6);
-    mplg_rule!(OCT_DIGIT6_RULE, OctDigit6, { Char('7') }, (), f);
-    mplg_rule!(DEC_DIGIT_RULE, DecDigit, OctDigit, (), DecDigit1);
-    mplg_rule!(DEC_DIGIT1_RULE, DecDigit1, { Char('8') }, (), DecDigit2);
-    mplg_rule!(DEC_DIGIT2_RULE, DecDigit2, { Char('9') }, (), f);
+    mplg_rule!(DEC_DIGIT_RULE, DecDigit, { CharRange('0', '9') }, (), f);
 
     // Comment
     mplg_rule!(
@@ -624,7 +428,7 @@ impl<'a> MplgRules {
         ()
     );
     mplg_rule!(ANY_EXCEPT_L_F_RULE, AnyExceptLF, AnyExceptLF1, ?, f);
-    mplg_rule!(ANY_EXCEPT_L_F1_RULE, AnyExceptLF1, EndOfLine, *, ());
+    mplg_rule!(ANY_EXCEPT_L_F1_RULE, AnyExceptLF1, !, EndOfLine, f);
 }
 
 impl<'a> Rules<U8SliceTerminal<'a>, MplgVariable> for MplgRules {
@@ -656,6 +460,10 @@ impl<'a> Rules<U8SliceTerminal<'a>, MplgVariable> for MplgRules {
             // Without Block
             ExprWithoutBlock => &Self::EXPR_WITHOUT_BLOCK_RULE,
             ExprWithoutBlock1 => &Self::EXPR_WITHOUT_BLOCK1_RULE,
+            ExprWithoutBlock2 => &Self::EXPR_WITHOUT_BLOCK2_RULE,
+            // Range
+            RangeExpr => &Self::RANGE_EXPR_RULE,
+            RangeExpr1 => &Self::RANGE_EXPR1_RULE,
 
             // Struct
             StructExpr => &Self::STRUCT_EXPR_RULE,
@@ -667,6 +475,7 @@ impl<'a> Rules<U8SliceTerminal<'a>, MplgVariable> for MplgRules {
             StructExprTuple1 => &Self::STRUCT_EXPR_TUPLE1_RULE,
             StructExprTuple2 => &Self::STRUCT_EXPR_TUPLE2_RULE,
             ZeroOrMoreExpr => &Self::ZERO_OR_MORE_EXPR_RULE,
+            ZeroOrMoreExpr1 => &Self::ZERO_OR_MORE_EXPR1_RULE,
 
             StructExprUnit => &Self::STRUCT_EXPR_UNIT_RULE,
 
@@ -692,11 +501,17 @@ impl<'a> Rules<U8SliceTerminal<'a>, MplgVariable> for MplgRules {
             MetasymbolLiteral1 => &Self::METASYMBOL_LITERAL1_RULE,
             MetasymbolLiteral2 => &Self::METASYMBOL_LITERAL2_RULE,
             MetasymbolLiteral3 => &Self::METASYMBOL_LITERAL3_RULE,
+            MetasymbolLiteral4 => &Self::METASYMBOL_LITERAL4_RULE,
+            MetasymbolLiteral5 => &Self::METASYMBOL_LITERAL5_RULE,
+            MetasymbolLiteral6 => &Self::METASYMBOL_LITERAL6_RULE,
             EmptyLiteral => &Self::EMPTY_RULE,
             FailureLiteral => &Self::FAILURE_LITERAL_RULE,
             AnyLiteral => &Self::ANY_LITERAL_RULE,
             ZeroOrMoreAny => &Self::ZERO_OR_MORE_ANY_RULE,
             AllLiteral => &Self::ALL_LITERAL_RULE,
+            OmitLiteral => &Self::OMIT_LITERAL_RULE,
+            AndLiteral => &Self::AND_LITERAL_RULE,
+            NotLiteral => &Self::NOT_LITERAL_RULE,
 
             // Original symbol
             OriginalSymbolExpr => &Self::ORIGINAL_SYMBOL_EXPR_RULE,
@@ -733,80 +548,16 @@ impl<'a> Rules<U8SliceTerminal<'a>, MplgVariable> for MplgRules {
             // Letters
             Alphabet => &Self::ALPHABET_RULE,
             // Lowercase
-            LowercaseAToF => &Self::LOWERCASE_A_TO_F_RULE,
-            LowercaseAToF1 => &Self::LOWERCASE_A_TO_F1_RULE,
-            LowercaseAToF2 => &Self::LOWERCASE_A_TO_F2_RULE,
-            LowercaseAToF3 => &Self::LOWERCASE_A_TO_F3_RULE,
-            LowercaseAToF4 => &Self::LOWERCASE_A_TO_F4_RULE,
-            LowercaseAToF5 => &Self::LOWERCASE_A_TO_F5_RULE,
-            LowercaseAToF6 => &Self::LOWERCASE_A_TO_F6_RULE,
             Lowercase => &Self::LOWERCASE_RULE,
-            Lowercase1 => &Self::LOWERCASE1_RULE,
-            Lowercase2 => &Self::LOWERCASE2_RULE,
-            Lowercase3 => &Self::LOWERCASE3_RULE,
-            Lowercase4 => &Self::LOWERCASE4_RULE,
-            Lowercase5 => &Self::LOWERCASE5_RULE,
-            Lowercase6 => &Self::LOWERCASE6_RULE,
-            Lowercase7 => &Self::LOWERCASE7_RULE,
-            Lowercase8 => &Self::LOWERCASE8_RULE,
-            Lowercase9 => &Self::LOWERCASE9_RULE,
-            Lowercase10 => &Self::LOWERCASE10_RULE,
-            Lowercase11 => &Self::LOWERCASE11_RULE,
-            Lowercase12 => &Self::LOWERCASE12_RULE,
-            Lowercase13 => &Self::LOWERCASE13_RULE,
-            Lowercase14 => &Self::LOWERCASE14_RULE,
-            Lowercase15 => &Self::LOWERCASE15_RULE,
-            Lowercase16 => &Self::LOWERCASE16_RULE,
-            Lowercase17 => &Self::LOWERCASE17_RULE,
-            Lowercase18 => &Self::LOWERCASE18_RULE,
-            Lowercase19 => &Self::LOWERCASE19_RULE,
-            Lowercase20 => &Self::LOWERCASE20_RULE,
             // Uppercase
-            UppercaseAToF => &Self::UPPERCASE_A_TO_F_RULE,
-            UppercaseAToF1 => &Self::UPPERCASE_A_TO_F1_RULE,
-            UppercaseAToF2 => &Self::UPPERCASE_A_TO_F2_RULE,
-            UppercaseAToF3 => &Self::UPPERCASE_A_TO_F3_RULE,
-            UppercaseAToF4 => &Self::UPPERCASE_A_TO_F4_RULE,
-            UppercaseAToF5 => &Self::UPPERCASE_A_TO_F5_RULE,
-            UppercaseAToF6 => &Self::UPPERCASE_A_TO_F6_RULE,
             Uppercase => &Self::UPPERCASE_RULE,
-            Uppercase1 => &Self::UPPERCASE1_RULE,
-            Uppercase2 => &Self::UPPERCASE2_RULE,
-            Uppercase3 => &Self::UPPERCASE3_RULE,
-            Uppercase4 => &Self::UPPERCASE4_RULE,
-            Uppercase5 => &Self::UPPERCASE5_RULE,
-            Uppercase6 => &Self::UPPERCASE6_RULE,
-            Uppercase7 => &Self::UPPERCASE7_RULE,
-            Uppercase8 => &Self::UPPERCASE8_RULE,
-            Uppercase9 => &Self::UPPERCASE9_RULE,
-            Uppercase10 => &Self::UPPERCASE10_RULE,
-            Uppercase11 => &Self::UPPERCASE11_RULE,
-            Uppercase12 => &Self::UPPERCASE12_RULE,
-            Uppercase13 => &Self::UPPERCASE13_RULE,
-            Uppercase14 => &Self::UPPERCASE14_RULE,
-            Uppercase15 => &Self::UPPERCASE15_RULE,
-            Uppercase16 => &Self::UPPERCASE16_RULE,
-            Uppercase17 => &Self::UPPERCASE17_RULE,
-            Uppercase18 => &Self::UPPERCASE18_RULE,
-            Uppercase19 => &Self::UPPERCASE19_RULE,
-            Uppercase20 => &Self::UPPERCASE20_RULE,
 
             QuoteEscape => &Self::QUOTE_ESCAPE_RULE,
             EndOfLine => &Self::END_OF_LINE_RULE,
             Space => &Self::SPACE_RULE,
 
             // Digits
-            BinDigit => &Self::BIN_DIGIT_RULE,
-            OctDigit => &Self::OCT_DIGIT_RULE,
-            OctDigit1 => &Self::OCT_DIGIT1_RULE,
-            OctDigit2 => &Self::OCT_DIGIT2_RULE,
-            OctDigit3 => &Self::OCT_DIGIT3_RULE,
-            OctDigit4 => &Self::OCT_DIGIT4_RULE,
-            OctDigit5 => &Self::OCT_DIGIT5_RULE,
-            OctDigit6 => &Self::OCT_DIGIT6_RULE,
             DecDigit => &Self::DEC_DIGIT_RULE,
-            DecDigit1 => &Self::DEC_DIGIT1_RULE,
-            DecDigit2 => &Self::DEC_DIGIT2_RULE,
 
             // Comment
             LineComment => &Self::LINE_COMMENT_RULE,
@@ -0,0 +1,31 @@
+use mpl::parser::Parser;
+use mpl::span::StartAndLenSpan;
+use mpl::trees::AST;
+use mpl_macro::Parse;
+
+#[derive(Parse, Debug)]
+#[mplg_inline = "
+Digits = { '0'..='9' } Digits1 / f
+Digits1 = Digits () / ()
+"]
+pub struct DigitsParser;
+
+type DigitsAST = AST<DigitsVariable, StartAndLenSpan<u32, u16>, ()>;
+
+#[test]
+fn derive_char_range() {
+    let parser = DigitsParser;
+
+    for (input, is_ok) in [("0123456789", true), ("", false), ("12a", false)] {
+        let input_data = input.as_bytes();
+        let all_of_the_span =
+            StartAndLenSpan::<u32, u16>::from_start_len(0, input_data.len() as u16);
+        let result: Result<DigitsAST, _> = parser.parse(
+            input_data,
+            &DigitsRules,
+            &DigitsVariable::Digits,
+            &all_of_the_span,
+        );
+        assert_eq!(result.is_ok(), is_ok);
+    }
+}
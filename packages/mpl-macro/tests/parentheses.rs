@@ -1,7 +1,5 @@
 use mpl::parser::Parser;
-use mpl::rules::{RightRule, Rules};
 use mpl::span::StartAndLenSpan;
-use mpl::symbols::U8SliceTerminal;
 use mpl::trees::AST;
 use mpl_macro::Parse;
 
@@ -9,18 +7,24 @@ use mpl_macro::Parse;
 #[mplg = "tests/parentheses.mplg"]
 pub struct ParenParser;
 
-impl<'a> Rules<U8SliceTerminal<'a>, ParenVariable> for ParenRules {
-    fn get(
-        &self,
-        variable: &ParenVariable,
-    ) -> Option<&RightRule<U8SliceTerminal<'a>, ParenVariable>> {
-        Some(match variable {
-            ParenVariable::Open => &Self::Open_RULE,
-            ParenVariable::Parentheses => &Self::Parentheses_RULE,
-            ParenVariable::Close => &Self::Close_RULE,
-        })
-    }
-}
+#[derive(Parse, Debug)]
+#[mplg = "tests/parentheses.mplg"]
+#[mplg(input = "str", terminal = "mpl::symbols::StrTerminal<'a>")]
+pub struct StrParenParser;
+
+#[derive(Parse, Debug)]
+#[mplg_inline = "
+// Parentheses
+Open = { Str(\"(\") } Parentheses / ()
+
+Parentheses = Open Close / f
+Close = { Char(')') } Open / f
+"]
+pub struct InlineParenParser;
+
+type ParenAST = AST<ParenVariable, StartAndLenSpan<u32, u16>, ()>;
+type InlineParenAST = AST<InlineParenVariable, StartAndLenSpan<u32, u16>, ()>;
+type StrParenAST = AST<StrParenVariable, StartAndLenSpan<u32, u16>, ()>;
 
 enum ParseResult {
     Ok,
@@ -50,10 +54,7 @@ fn derive_grammar_from_file() {
         // all of the span
         let all_of_the_span =
             StartAndLenSpan::<u32, u16>::from_start_len(0, input_data.len() as u16);
-        let result: Result<
-            AST<ParenVariable, StartAndLenSpan<u32, u16>, ()>,
-            AST<ParenVariable, StartAndLenSpan<u32, u16>, ()>,
-        > = parser.parse(
+        let result: Result<ParenAST, _> = parser.parse(
             input_data,
             &ParenRules,
             &ParenVariable::Open,
@@ -65,3 +66,43 @@ fn derive_grammar_from_file() {
         }
     }
 }
+
+#[test]
+fn derive_grammar_for_str() {
+    let parser = StrParenParser;
+
+    for (input, parse_result) in INPUTS {
+        let all_of_the_span = StartAndLenSpan::<u32, u16>::from_start_len(0, input.len() as u16);
+        let result: Result<StrParenAST, _> = parser.parse(
+            input,
+            &StrParenRules,
+            &StrParenVariable::Open,
+            &all_of_the_span,
+        );
+        match parse_result {
+            ParseResult::Ok => assert!(result.is_ok()),
+            ParseResult::Err => assert!(result.is_err()),
+        }
+    }
+}
+
+#[test]
+fn derive_inline_grammar() {
+    let parser = InlineParenParser;
+
+    for (input, parse_result) in INPUTS {
+        let input_data = input.as_bytes();
+        let all_of_the_span =
+            StartAndLenSpan::<u32, u16>::from_start_len(0, input_data.len() as u16);
+        let result: Result<InlineParenAST, _> = parser.parse(
+            input_data,
+            &InlineParenRules,
+            &InlineParenVariable::Open,
+            &all_of_the_span,
+        );
+        match parse_result {
+            ParseResult::Ok => assert!(result.is_ok()),
+            ParseResult::Err => assert!(result.is_err()),
+        }
+    }
+}
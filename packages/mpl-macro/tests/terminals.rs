@@ -0,0 +1,138 @@
+use mpl::parser::Parser;
+use mpl::span::{Len, Span, Start, StartAndLenSpan};
+use mpl::symbols::{Metasymbol, Terminal};
+use mpl::trees::AST;
+use mpl_macro::Parse;
+
+#[derive(Parse, Debug)]
+#[mplg_inline = "
+Zeros = { Element(0) } Zeros1 / f
+Zeros1 = Zeros () / ()
+"]
+#[mplg(input = "[i32]", terminal = "mpl::symbols::SliceTerminal<'a, i32>")]
+pub struct ZerosParser;
+
+/// Kind of a token.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Kind {
+    Number,
+    Plus,
+}
+
+/// Terminal symbol without lifetime parameter.
+#[derive(Clone, Debug, PartialEq)]
+pub enum KindTerminal {
+    Number,
+    Plus,
+}
+
+impl<'a, V, P, L, O> Terminal<'a, [Kind], V, StartAndLenSpan<P, L>, P, O> for KindTerminal
+where
+    P: Start<[Kind], L>,
+    L: Len<[Kind], P>,
+{
+    fn eval(
+        &self,
+        input: &'a [Kind],
+        pos: P,
+        max_pos: &P,
+    ) -> Result<AST<V, StartAndLenSpan<P, L>, O>, AST<V, StartAndLenSpan<P, L>, O>> {
+        let kind = match self {
+            Self::Number => Kind::Number,
+            Self::Plus => Kind::Plus,
+        };
+        let span = StartAndLenSpan::from_lo_len(pos.clone(), 1, input);
+        let hi = span.hi(input);
+        let ast = AST::from_leaf(Metasymbol::Omit.into(), span);
+        match input.get(P::into_usize(pos, input)) {
+            Some(k) if &hi <= max_pos && *k == kind => Ok(ast),
+            _ => Err(ast),
+        }
+    }
+}
+
+#[derive(Parse, Debug)]
+#[mplg_inline = "
+Sum = { Number } Sum1 / f
+Sum1 = { Plus } Sum / ()
+"]
+#[mplg(input = "[Kind]", terminal = "KindTerminal")]
+pub struct SumParser;
+
+/// Terminal symbol implementing `Terminal` only for `u32` positions.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AsciiTerminal {
+    Digit,
+}
+
+impl<'a, V, O> Terminal<'a, str, V, StartAndLenSpan<u32, u16>, u32, O> for AsciiTerminal {
+    fn eval(
+        &self,
+        input: &'a str,
+        pos: u32,
+        max_pos: &u32,
+    ) -> Result<AST<V, StartAndLenSpan<u32, u16>, O>, AST<V, StartAndLenSpan<u32, u16>, O>> {
+        let is_digit = pos < *max_pos && input.as_bytes()[pos as usize].is_ascii_digit();
+        let ast = AST::from_leaf(
+            Metasymbol::Omit.into(),
+            StartAndLenSpan::from_start_len(pos, is_digit as u16),
+        );
+        if is_digit {
+            Ok(ast)
+        } else {
+            Err(ast)
+        }
+    }
+}
+
+#[derive(Parse, Debug)]
+#[mplg_inline = "
+Number = { Digit } Number1 / f
+Number1 = Number () / ()
+"]
+#[mplg(input = "str", terminal = "AsciiTerminal")]
+pub struct NumberParser;
+
+#[test]
+fn derive_slice_terminal() {
+    let parser = ZerosParser;
+
+    for (input, is_ok) in [(&[0, 0, 0][..], true), (&[], false), (&[0, 1], false)] {
+        let all_of_the_span = StartAndLenSpan::<u32, u16>::from_start_len(0, input.len() as u16);
+        let result: Result<AST<ZerosVariable, _, ()>, _> =
+            parser.parse(input, &ZerosRules, &ZerosVariable::Zeros, &all_of_the_span);
+        assert_eq!(result.is_ok(), is_ok);
+    }
+}
+
+#[test]
+fn derive_terminal_without_lifetime() {
+    let parser = SumParser;
+
+    for (input, is_ok) in [
+        (&[Kind::Number, Kind::Plus, Kind::Number][..], true),
+        (&[Kind::Number, Kind::Plus], false),
+        (&[Kind::Plus], false),
+    ] {
+        let all_of_the_span = StartAndLenSpan::<u32, u16>::from_start_len(0, input.len() as u16);
+        let result: Result<AST<SumVariable, _, ()>, _> =
+            parser.parse(input, &SumRules, &SumVariable::Sum, &all_of_the_span);
+        assert_eq!(result.is_ok(), is_ok);
+    }
+}
+
+#[test]
+fn derive_terminal_for_concrete_position() {
+    let parser = NumberParser;
+
+    for (input, is_ok) in [("0123456789", true), ("", false), ("12a", false)] {
+        let all_of_the_span = StartAndLenSpan::<u32, u16>::from_start_len(0, input.len() as u16);
+        let result: Result<AST<NumberVariable, _, ()>, _> = parser.parse(
+            input,
+            &NumberRules,
+            &NumberVariable::Number,
+            &all_of_the_span,
+        );
+        assert_eq!(result.is_ok(), is_ok);
+    }
+}
@@ -0,0 +1,397 @@
+//! Analysis
+//!
+//! Static analysis of rules, such as finding left recursion which makes `eval` recurse infinitely.
+
+use crate::rules::{RightRule, Rules};
+use crate::symbols::{Metasymbol, TerminalSymbol, E};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// Result of analyzing rules.
+///
+/// Each `Vec` is in order of the given variables.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Analysis<V> {
+    /// Variables reachable from the start variable.
+    pub reachable: Vec<V>,
+    /// Variables not reachable from the start variable.
+    pub unreachable: Vec<V>,
+    /// Variables which can succeed without consuming input.
+    pub nullable: Vec<V>,
+    /// Left-recursive cycles.
+    ///
+    /// A cycle of one variable is direct left recursion such as `A = A x / y`.
+    pub left_recursions: Vec<Vec<V>>,
+}
+
+impl<V> Analysis<V>
+where
+    V: Clone + Eq + Hash,
+{
+    pub fn new<T, R>(rules: &R, variables: &[V], start_variable: &V) -> Self
+    where
+        R: Rules<T, V>,
+    {
+        let reachable_variables = reachable(rules, start_variable);
+        let nullable_variables = nullable(rules, variables);
+
+        let (reachable, unreachable) = variables
+            .iter()
+            .cloned()
+            .partition(|v| reachable_variables.contains(v));
+
+        Self {
+            reachable,
+            unreachable,
+            nullable: variables
+                .iter()
+                .filter(|v| nullable_variables.contains(v))
+                .cloned()
+                .collect(),
+            left_recursions: left_recursions(rules, variables),
+        }
+    }
+
+    /// Returns true if there is any left-recursive cycle.
+    pub fn is_left_recursive(&self) -> bool {
+        !self.left_recursions.is_empty()
+    }
+}
+
+/// Returns variables reachable from `start_variable`, including itself.
+pub fn reachable<T, V, R>(rules: &R, start_variable: &V) -> HashSet<V>
+where
+    V: Clone + Eq + Hash,
+    R: Rules<T, V>,
+{
+    let mut visited = HashSet::new();
+    let mut variables = vec![start_variable.clone()];
+    visited.insert(start_variable.clone());
+
+    while let Some(variable) = variables.pop() {
+        if let Some(right_rule) = rules.get(&variable) {
+            for e in [
+                &right_rule.first.lhs,
+                &right_rule.first.rhs,
+                &right_rule.second.0,
+            ] {
+                if let E::V(v) = e {
+                    if visited.insert(v.clone()) {
+                        variables.push(v.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    visited
+}
+
+/// Returns variables which can succeed without consuming input.
+///
+/// `()`, `*`, `_` and `&` are nullable, and `f`, `?` and `!` are not, since `!` without an operand always fails.
+/// `& C` and `! C` are nullable.
+/// Original terminal symbols are assumed to consume input.
+pub fn nullable<T, V, R>(rules: &R, variables: &[V]) -> HashSet<V>
+where
+    V: Clone + Eq + Hash,
+    R: Rules<T, V>,
+{
+    let mut nullable = HashSet::new();
+
+    loop {
+        let mut changed = false;
+
+        for variable in variables {
+            if nullable.contains(variable) {
+                continue;
+            }
+            if let Some(right_rule) = rules.get(variable) {
+                if is_nullable_right_rule(right_rule, &nullable) {
+                    nullable.insert(variable.clone());
+                    changed = true;
+                }
+            }
+        }
+
+        if !changed {
+            return nullable;
+        }
+    }
+}
+
+/// Returns left-recursive cycles.
+///
+/// In `A = B C / D`, `B` and `D` are evaluated at the same position as `A`, and so is `C` if `B` is nullable.
+/// Each cycle is a strongly connected component of these variables.
+pub fn left_recursions<T, V, R>(rules: &R, variables: &[V]) -> Vec<Vec<V>>
+where
+    V: Clone + Eq + Hash,
+    R: Rules<T, V>,
+{
+    let nullable = nullable(rules, variables);
+
+    let mut graph: HashMap<&V, Vec<&V>> = HashMap::new();
+    for variable in variables {
+        let mut edges = Vec::new();
+        if let Some(right_rule) = rules.get(variable) {
+            if let E::V(v) = &right_rule.first.lhs {
+                edges.push(v);
+            }
+            if is_lookahead(&right_rule.first.lhs)
+                || is_nullable_e(&right_rule.first.lhs, &nullable)
+            {
+                if let E::V(v) = &right_rule.first.rhs {
+                    edges.push(v);
+                }
+            }
+            if let E::V(v) = &right_rule.second.0 {
+                edges.push(v);
+            }
+        }
+        graph.insert(variable, edges);
+    }
+
+    let mut tarjan = Tarjan {
+        graph: &graph,
+        index: 0,
+        indices: HashMap::new(),
+        low_links: HashMap::new(),
+        stack: Vec::new(),
+        on_stack: HashSet::new(),
+        components: Vec::new(),
+    };
+    for variable in variables {
+        if !tarjan.indices.contains_key(variable) {
+            tarjan.strong_connect(variable);
+        }
+    }
+
+    let mut left_recursions: Vec<Vec<V>> = tarjan
+        .components
+        .into_iter()
+        .filter(|component| component.len() > 1 || graph[component[0]].contains(&component[0]))
+        .map(|component| {
+            variables
+                .iter()
+                .filter(|v| component.contains(v))
+                .cloned()
+                .collect()
+        })
+        .collect();
+    left_recursions.sort_by_key(|cycle: &Vec<V>| variables.iter().position(|v| v == &cycle[0]));
+
+    left_recursions
+}
+
+fn is_nullable_right_rule<T, V>(right_rule: &RightRule<T, V>, nullable: &HashSet<V>) -> bool
+where
+    V: Eq + Hash,
+{
+    // `& C` and `! C` consume no input whether `C` is nullable or not.
+    is_lookahead(&right_rule.first.lhs)
+        || (is_nullable_e(&right_rule.first.lhs, nullable)
+            && is_nullable_e(&right_rule.first.rhs, nullable))
+        || is_nullable_e(&right_rule.second.0, nullable)
+}
+
+/// Returns true if `e` is `&` or `!`, which makes `C` of `A = B C / D` its operand.
+fn is_lookahead<T, V>(e: &E<T, V>) -> bool {
+    matches!(
+        e,
+        E::T(TerminalSymbol::Metasymbol(
+            Metasymbol::And | Metasymbol::Not
+        ))
+    )
+}
+
+fn is_nullable_e<T, V>(e: &E<T, V>, nullable: &HashSet<V>) -> bool
+where
+    V: Eq + Hash,
+{
+    match e {
+        E::T(TerminalSymbol::Original(_)) => false,
+        E::T(TerminalSymbol::Metasymbol(metasymbol)) => match metasymbol {
+            Metasymbol::Empty | Metasymbol::All | Metasymbol::Omit | Metasymbol::And => true,
+            // `!` without an operand always fails.
+            Metasymbol::Failure | Metasymbol::Not => false,
+            Metasymbol::Any(n) => *n == 0,
+        },
+        E::V(v) => nullable.contains(v),
+    }
+}
+
+/// Tarjan's strongly connected components algorithm.
+struct Tarjan<'a, V> {
+    graph: &'a HashMap<&'a V, Vec<&'a V>>,
+    index: usize,
+    indices: HashMap<&'a V, usize>,
+    low_links: HashMap<&'a V, usize>,
+    stack: Vec<&'a V>,
+    on_stack: HashSet<&'a V>,
+    components: Vec<Vec<&'a V>>,
+}
+
+impl<'a, V> Tarjan<'a, V>
+where
+    V: Eq + Hash,
+{
+    fn strong_connect(&mut self, v: &'a V) {
+        self.indices.insert(v, self.index);
+        self.low_links.insert(v, self.index);
+        self.index += 1;
+        self.stack.push(v);
+        self.on_stack.insert(v);
+
+        let graph = self.graph;
+        for w in graph.get(v).into_iter().flatten() {
+            if !self.indices.contains_key(w) {
+                self.strong_connect(w);
+                let low_link = self.low_links[v].min(self.low_links[w]);
+                self.low_links.insert(v, low_link);
+            } else if self.on_stack.contains(w) {
+                let low_link = self.low_links[v].min(self.indices[w]);
+                self.low_links.insert(v, low_link);
+            }
+        }
+
+        if self.low_links[v] == self.indices[v] {
+            let mut component = Vec::new();
+            while let Some(w) = self.stack.pop() {
+                self.on_stack.remove(w);
+                component.push(w);
+                if w == v {
+                    break;
+                }
+            }
+            self.components.push(component);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::RightRuleKind::*;
+    use crate::symbols::{StrTerminal, StrTerminal::*};
+
+    #[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
+    enum TestVariable {
+        Start,
+        Direct,
+        Indirect1,
+        Indirect2,
+        Nullable,
+        Rest,
+        Unreachable,
+    }
+
+    /// ```
+    /// Start = Nullable Direct / Indirect1
+    /// Direct = Direct 'x' / 'y'
+    /// Indirect1 = Nullable Indirect2 / f
+    /// Indirect2 = 'z' () / Indirect1
+    /// Nullable = 'n' Nullable / ()
+    /// Rest = * () / f
+    /// Unreachable = Start Rest / f
+    /// ```
+    #[test]
+    fn analysis() {
+        use TestVariable::*;
+
+        let mut rules: HashMap<TestVariable, RightRule<StrTerminal, TestVariable>> = HashMap::new();
+        rules.insert(
+            Start,
+            RightRule::from_right_rule_kind((V(Nullable), V(Direct)), V(Indirect1)),
+        );
+        rules.insert(
+            Direct,
+            RightRule::from_right_rule_kind((V(Direct), T(Char('x'))), T(Char('y'))),
+        );
+        rules.insert(
+            Indirect1,
+            RightRule::from_right_rule_kind((V(Nullable), V(Indirect2)), Failure),
+        );
+        rules.insert(
+            Indirect2,
+            RightRule::from_right_rule_kind((T(Char('z')), Empty), V(Indirect1)),
+        );
+        rules.insert(
+            Nullable,
+            RightRule::from_right_rule_kind((T(Char('n')), V(Nullable)), Empty),
+        );
+        rules.insert(Rest, RightRule::from_right_rule_kind((All, Empty), Failure));
+        rules.insert(
+            Unreachable,
+            RightRule::from_right_rule_kind((V(Start), V(Rest)), Failure),
+        );
+
+        let variables = [
+            Start,
+            Direct,
+            Indirect1,
+            Indirect2,
+            Nullable,
+            Rest,
+            Unreachable,
+        ];
+        let analysis = Analysis::new(&rules, &variables, &Start);
+
+        assert_eq!(
+            analysis.reachable,
+            vec![Start, Direct, Indirect1, Indirect2, Nullable]
+        );
+        assert_eq!(analysis.unreachable, vec![Rest, Unreachable]);
+        assert_eq!(analysis.nullable, vec![Nullable, Rest]);
+        assert!(analysis.is_left_recursive());
+        assert_eq!(
+            analysis.left_recursions,
+            vec![vec![Direct], vec![Indirect1, Indirect2]]
+        );
+
+        // Without left recursion
+        rules.insert(
+            Direct,
+            RightRule::from_right_rule_kind((T(Char('y')), V(Direct)), Empty),
+        );
+        rules.insert(
+            Indirect1,
+            RightRule::from_right_rule_kind((T(Char('i')), V(Indirect2)), Failure),
+        );
+        let analysis = Analysis::new(&rules, &variables, &Start);
+
+        assert!(!analysis.is_left_recursive());
+        assert_eq!(
+            analysis.nullable,
+            vec![Start, Direct, Nullable, Rest, Unreachable]
+        );
+    }
+
+    /// ```
+    /// Alone = () ! / f
+    /// Lookahead = ! 'x' / f
+    /// NotLeftRecursive = ! NotLeftRecursive / f
+    /// ```
+    #[test]
+    fn not_without_operand() {
+        let mut rules: HashMap<&str, RightRule<StrTerminal, &str>> = HashMap::new();
+        rules.insert(
+            "Alone",
+            RightRule::from_right_rule_kind((Empty, Not), Failure),
+        );
+        rules.insert(
+            "Lookahead",
+            RightRule::from_right_rule_kind((Not, T(Char('x'))), Failure),
+        );
+        rules.insert(
+            "NotLeftRecursive",
+            RightRule::from_right_rule_kind((Not, V("NotLeftRecursive")), Failure),
+        );
+
+        let variables = ["Alone", "Lookahead", "NotLeftRecursive"];
+        let analysis = Analysis::new(&rules, &variables, &"Alone");
+
+        assert_eq!(analysis.nullable, vec!["Lookahead", "NotLeftRecursive"]);
+        assert_eq!(analysis.left_recursions, vec![vec!["NotLeftRecursive"]]);
+    }
+}
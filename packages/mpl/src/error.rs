@@ -0,0 +1,225 @@
+//! Error
+
+use crate::symbols::TerminalSymbol;
+use crate::trees::AST;
+use std::error::Error;
+use std::fmt;
+
+/// Error returned by [`Parser::parse`](crate::parser::Parser::parse).
+///
+/// T is terminal symbols.
+/// V is (enum of) Variables.
+/// S is Span.
+/// P is position.
+/// O is output type.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError<T, V, S, P, O = ()> {
+    pub kind: ParseErrorKind<V, S, P, O>,
+    pub farthest_failure: FarthestFailure<T, V, P>,
+}
+
+impl<T, V, S, P, O> ParseError<T, V, S, P, O> {
+    pub fn new(
+        kind: ParseErrorKind<V, S, P, O>,
+        farthest_failure: FarthestFailure<T, V, P>,
+    ) -> Self {
+        Self {
+            kind,
+            farthest_failure,
+        }
+    }
+}
+
+/// Kind of [`ParseError`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseErrorKind<V, S, P, O = ()> {
+    /// The start variable failed.
+    Failure(AST<V, S, O>),
+    /// The start variable succeeded, but consumed only up to `hi`.
+    PartialMatch { ast: AST<V, S, O>, hi: P },
+    /// There is no right rule for the variable.
+    MissingRule(V),
+    /// A limit of [`ParseOptions`](crate::parser::ParseOptions) was exceeded.
+    LimitExceeded(Limit),
+}
+
+impl<V, S, P, O> ParseErrorKind<V, S, P, O> {
+    /// Returns true if Self::Failure
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Self::Failure(_))
+    }
+
+    /// Returns true if Self::PartialMatch
+    pub fn is_partial_match(&self) -> bool {
+        matches!(self, Self::PartialMatch { .. })
+    }
+
+    /// Returns true if Self::MissingRule
+    pub fn is_missing_rule(&self) -> bool {
+        matches!(self, Self::MissingRule(_))
+    }
+
+    /// Returns true if Self::LimitExceeded
+    pub fn is_limit_exceeded(&self) -> bool {
+        matches!(self, Self::LimitExceeded(_))
+    }
+}
+
+/// Limit of [`ParseOptions`](crate::parser::ParseOptions) which was exceeded.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Limit {
+    /// Max depth of variables being evaluated.
+    Depth(usize),
+    /// Max number of evaluated variables.
+    Steps(usize),
+    /// Deadline of the parse.
+    Deadline,
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Depth(max_depth) => write!(f, "exceeded max depth of {}", max_depth),
+            Self::Steps(max_steps) => write!(f, "exceeded max steps of {}", max_steps),
+            Self::Deadline => write!(f, "exceeded deadline"),
+        }
+    }
+}
+
+/// Farthest position where a terminal symbol failed.
+///
+/// `Display` shows the expected terminal symbols like `expected '(' or ")" at 7`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FarthestFailure<T, V, P> {
+    /// `None` if no terminal symbol failed.
+    pub pos: Option<P>,
+    /// Variables being evaluated when `pos` was first reached, from the start variable.
+    pub stack: Vec<V>,
+    /// Terminal symbols which failed at `pos`.
+    pub expected: Vec<TerminalSymbol<T>>,
+}
+
+impl<T, V, P> Default for FarthestFailure<T, V, P> {
+    fn default() -> Self {
+        Self {
+            pos: None,
+            stack: Vec::new(),
+            expected: Vec::new(),
+        }
+    }
+}
+
+impl<T, V, P> FarthestFailure<T, V, P>
+where
+    T: Clone + PartialEq,
+    V: Clone,
+    P: PartialOrd,
+{
+    /// Records that `terminal_symbol` failed at `pos` while evaluating `stack`.
+    pub fn record(&mut self, terminal_symbol: &TerminalSymbol<T>, pos: P, stack: &[V]) {
+        match &self.pos {
+            Some(farthest) if &pos < farthest => {}
+            Some(farthest) if &pos == farthest => {
+                if !self.expected.contains(terminal_symbol) {
+                    self.expected.push(terminal_symbol.clone());
+                }
+            }
+            _ => {
+                self.pos = Some(pos);
+                self.stack = stack.to_vec();
+                self.expected = vec![terminal_symbol.clone()];
+            }
+        }
+    }
+}
+
+impl<T, V, P> fmt::Display for FarthestFailure<T, V, P>
+where
+    T: fmt::Display,
+    P: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pos = match &self.pos {
+            Some(pos) => pos,
+            None => return write!(f, "no terminal symbol failed"),
+        };
+
+        write!(f, "expected ")?;
+        let len = self.expected.len();
+        for (i, terminal_symbol) in self.expected.iter().enumerate() {
+            if i == 0 {
+                write!(f, "{}", terminal_symbol)?;
+            } else if i + 1 == len {
+                write!(f, " or {}", terminal_symbol)?;
+            } else {
+                write!(f, ", {}", terminal_symbol)?;
+            }
+        }
+        write!(f, " at {}", pos)
+    }
+}
+
+impl<T, V, S, P, O> fmt::Display for ParseError<T, V, S, P, O>
+where
+    T: fmt::Display,
+    V: fmt::Debug,
+    P: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ParseErrorKind::MissingRule(variable) => {
+                write!(f, "no right rule for variable {:?}", variable)
+            }
+            ParseErrorKind::LimitExceeded(limit) => limit.fmt(f),
+            ParseErrorKind::Failure(_) | ParseErrorKind::PartialMatch { .. }
+                if self.farthest_failure.pos.is_some() =>
+            {
+                self.farthest_failure.fmt(f)
+            }
+            ParseErrorKind::Failure(_) => write!(f, "failed to parse"),
+            ParseErrorKind::PartialMatch { hi, .. } => write!(f, "parsed only up to {}", hi),
+        }
+    }
+}
+
+impl<T, V, S, P, O> Error for ParseError<T, V, S, P, O>
+where
+    T: fmt::Debug + fmt::Display,
+    V: fmt::Debug,
+    S: fmt::Debug,
+    P: fmt::Debug + fmt::Display,
+    O: fmt::Debug,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::symbols::{Metasymbol, StrTerminal};
+
+    #[test]
+    fn farthest_failure() {
+        let mut farthest_failure = FarthestFailure::<StrTerminal, (), u32>::default();
+        assert_eq!(farthest_failure.to_string(), "no terminal symbol failed");
+
+        farthest_failure.record(&TerminalSymbol::Original(StrTerminal::Char('(')), 3, &[()]);
+        farthest_failure.record(
+            &TerminalSymbol::Original(StrTerminal::Char('(')),
+            7,
+            &[(), ()],
+        );
+        farthest_failure.record(&TerminalSymbol::Original(StrTerminal::Str(")")), 7, &[()]);
+        farthest_failure.record(&TerminalSymbol::Original(StrTerminal::Char('(')), 7, &[]);
+        farthest_failure.record(&TerminalSymbol::Original(StrTerminal::Str("]")), 5, &[]);
+
+        assert_eq!(farthest_failure.pos, Some(7));
+        assert_eq!(farthest_failure.stack, vec![(), ()]);
+        assert_eq!(farthest_failure.to_string(), "expected '(' or \")\" at 7");
+
+        farthest_failure.record(&Metasymbol::Any(2).into(), 7, &[]);
+        assert_eq!(
+            farthest_failure.to_string(),
+            "expected '(', \")\" or ?? at 7"
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use crate::input::Input;
+
+/// Order of bits in a byte or in a field.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum BitOrder {
+    /// The most significant bit comes first, like MPEG and FLAC.
+    MsbFirst,
+    /// The least significant bit comes first, like DEFLATE.
+    LsbFirst,
+}
+
+/// Bit-addressed input over bytes.
+///
+/// Positions and lengths of spans are in bits, and `?` consumes a bit.
+/// `order` is the order of bits in each byte.
+///
+/// ```
+/// use mpl::input::{BitOrder, BitSlice};
+///
+/// let bits = BitSlice::new(&[0b1010_0000], BitOrder::MsbFirst);
+///
+/// assert_eq!(bits.len(), 8);
+/// assert_eq!(bits.bit(0), Some(true));
+/// assert_eq!(bits.read(0, 3, BitOrder::MsbFirst), Some(0b101));
+/// assert_eq!(bits.read(0, 3, BitOrder::LsbFirst), Some(0b101));
+/// assert_eq!(bits.read(0, 2, BitOrder::LsbFirst), Some(0b01));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct BitSlice<'a> {
+    bytes: &'a [u8],
+    order: BitOrder,
+}
+
+impl<'a> BitSlice<'a> {
+    pub fn new(bytes: &'a [u8], order: BitOrder) -> Self {
+        Self { bytes, order }
+    }
+
+    pub fn bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    pub fn order(&self) -> BitOrder {
+        self.order
+    }
+
+    /// Returns the number of bits.
+    pub fn len(&self) -> usize {
+        self.bytes.len() * 8
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Returns the `i`th bit.
+    pub fn bit(&self, i: usize) -> Option<bool> {
+        let byte = self.bytes.get(i / 8)?;
+        let shift = match self.order {
+            BitOrder::MsbFirst => 7 - i % 8,
+            BitOrder::LsbFirst => i % 8,
+        };
+        Some(byte >> shift & 1 == 1)
+    }
+
+    /// Returns the value of the `n`-bit field from `lo`, whose bits are in `order`.
+    ///
+    /// Returns `None` if `n` is more than 64 or the field is out of input.
+    pub fn read(&self, lo: usize, n: usize, order: BitOrder) -> Option<u64> {
+        if n > 64 || lo.checked_add(n)? > self.len() {
+            return None;
+        }
+        let mut value = 0;
+        for k in 0..n {
+            let bit = u64::from(self.bit(lo + k)?);
+            match order {
+                BitOrder::MsbFirst => value = value << 1 | bit,
+                BitOrder::LsbFirst => value |= bit << k,
+            }
+        }
+        Some(value)
+    }
+}
+
+/// A unit is a bit.
+impl Input for BitSlice<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read() {
+        let msb = BitSlice::new(&[0b1100_0001, 0xff], BitOrder::MsbFirst);
+        assert_eq!(msb.read(0, 2, BitOrder::MsbFirst), Some(0b11));
+        assert_eq!(msb.read(6, 3, BitOrder::MsbFirst), Some(0b011));
+        assert_eq!(msb.read(0, 16, BitOrder::MsbFirst), Some(0xc1ff));
+        assert_eq!(msb.read(0, 0, BitOrder::MsbFirst), Some(0));
+        assert_eq!(msb.read(9, 8, BitOrder::MsbFirst), None);
+
+        let lsb = BitSlice::new(&[0b0000_0101], BitOrder::LsbFirst);
+        // BFINAL and BTYPE of DEFLATE
+        assert_eq!(lsb.read(0, 1, BitOrder::LsbFirst), Some(1));
+        assert_eq!(lsb.read(1, 2, BitOrder::LsbFirst), Some(0b10));
+        assert_eq!(lsb.bit(8), None);
+
+        let bytes = [0xff; 9];
+        let long = BitSlice::new(&bytes, BitOrder::MsbFirst);
+        assert_eq!(long.read(0, 64, BitOrder::MsbFirst), Some(u64::MAX));
+        assert_eq!(long.read(0, 65, BitOrder::MsbFirst), None);
+    }
+}
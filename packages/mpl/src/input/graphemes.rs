@@ -0,0 +1,61 @@
+use crate::input::Input;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// `str` input whose unit is an extended grapheme cluster.
+///
+/// Positions and lengths of spans are in bytes as in `str`, and `?` consumes an extended grapheme cluster instead of a `char`.
+///
+/// ```
+/// use mpl::input::{Graphemes, Input};
+///
+/// // 'e' and a combining acute accent
+/// let input = Graphemes::new("e\u{301}x");
+///
+/// assert_eq!(input.len_of_units(0, 1), Some(3));
+/// assert_eq!("e\u{301}x".len_of_units(0, 1), Some(1));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Graphemes<'a> {
+    s: &'a str,
+}
+
+impl<'a> Graphemes<'a> {
+    pub fn new(s: &'a str) -> Self {
+        Self { s }
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        self.s
+    }
+}
+
+impl Input for Graphemes<'_> {
+    fn len_of_units(&self, lo: usize, n: usize) -> Option<usize> {
+        let rest = self.s.get(lo..)?;
+        if n == 0 {
+            return Some(0);
+        }
+
+        rest.grapheme_indices(true)
+            .map(|(i, g)| i + g.len())
+            .nth(n - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn len_of_units() {
+        // 'e' and a combining acute accent
+        let input = Graphemes::new("e\u{301}x");
+
+        assert_eq!(input.len_of_units(0, 0), Some(0));
+        assert_eq!(input.len_of_units(0, 1), Some(3));
+        assert_eq!(input.len_of_units(0, 2), Some(4));
+        assert_eq!(input.len_of_units(0, 3), None);
+        // Not at a char boundary
+        assert_eq!(input.len_of_units(2, 1), None);
+    }
+}
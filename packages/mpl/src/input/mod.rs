@@ -1,5 +1,12 @@
 //! Input
 
+pub use self::bits::{BitOrder, BitSlice};
+#[cfg(feature = "grapheme")]
+pub use self::graphemes::Graphemes;
+
+mod bits;
+#[cfg(feature = "grapheme")]
+mod graphemes;
 mod slice;
 mod str;
 
@@ -14,4 +21,11 @@ mod str;
 ///
 /// impl Input for ExtStr {}
 /// ```
-pub trait Input {}
+pub trait Input {
+    /// Returns the length of `n` units of input from `lo`, or `None` if `lo` is not at the start of a unit or there are fewer than `n` units left.
+    ///
+    /// `?` consumes a unit. A unit is a single element by default, and a `char` for `str`.
+    fn len_of_units(&self, _lo: usize, n: usize) -> Option<usize> {
+        Some(n)
+    }
+}
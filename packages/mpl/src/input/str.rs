@@ -1,3 +1,35 @@
 use crate::input::Input;
 
-impl Input for str {}
+/// A unit is a `char`. See [`Graphemes`](crate::input::Graphemes) for extended grapheme clusters.
+impl Input for str {
+    fn len_of_units(&self, lo: usize, n: usize) -> Option<usize> {
+        let rest = self.get(lo..)?;
+        if n == 0 {
+            return Some(0);
+        }
+
+        rest.char_indices()
+            .map(|(i, c)| i + c.len_utf8())
+            .nth(n - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn len_of_units() {
+        let input = "aあ🦀";
+
+        assert_eq!(input.len_of_units(0, 0), Some(0));
+        assert_eq!(input.len_of_units(0, 1), Some(1));
+        assert_eq!(input.len_of_units(0, 2), Some(4));
+        assert_eq!(input.len_of_units(1, 2), Some(7));
+        assert_eq!(input.len_of_units(0, 4), None);
+        assert_eq!(input.len_of_units(8, 0), Some(0));
+        assert_eq!(input.len_of_units(8, 1), None);
+        // Not at a char boundary
+        assert_eq!(input.len_of_units(2, 1), None);
+    }
+}
@@ -29,6 +29,7 @@
 //! ### Example
 //! ```rust
 //! use crate::ParenthesesVariable::*;
+//! use mpl::error::ParseError;
 //! use mpl::parser::Parser;
 //! use mpl::rules::{RightRule, RightRuleKind::*, Rules};
 //! use mpl::span::{StartAndLenSpan, Start, Len};
@@ -88,7 +89,7 @@
 //!
 //!     let result: Result<
 //!         AST<ParenthesesVariable, StartAndLenSpan<u32, u16>, ()>,
-//!         AST<ParenthesesVariable, StartAndLenSpan<u32, u16>, ()>,
+//!         ParseError<StrTerminal, ParenthesesVariable, StartAndLenSpan<u32, u16>, u32, ()>,
 //!     > = parser.parse(input, &rules, &Open, &all_of_the_span);
 //!
 //!     if let Ok(ast) = result {
@@ -113,9 +114,9 @@
 //! - `T` is an union of `Σ` or `M` (Σ &cup; M) (`M` (= {(), f}) is a finite set of metasymbols).
 //! - `R` is a finite set of rules of the form
 //!     - `A = B C / D`  
-//!     A in V (A &isin; V),  
-//!     B, C, D in E (E = T &cup; V) (T &cap; V = &empty;) (B, C, D &isin; E).  
-//!     For any variable A there is exactly one rule with A to the left of `=`.
+//!       A in V (A &isin; V),  
+//!       B, C, D in E (E = T &cup; V) (T &cap; V = &empty;) (B, C, D &isin; E).  
+//!       For any variable A there is exactly one rule with A to the left of `=`.
 //! - S in V (S &isin; V) is the start variable.
 //!
 //! #### Empty
@@ -142,6 +143,8 @@
 //! Any = ? () / f
 //! ```
 //!
+//! For `str`, a single input is a `char`, so `?` never splits a multi-byte character. With the `grapheme` feature, `Graphemes` wraps `str` input so that a single input is an extended grapheme cluster instead.
+//!
 //! To extend the difinition of MPL grammar, let ? &isin; M.
 //!
 //! #### All
@@ -154,12 +157,60 @@
 //! Same as `All = ? All / ()`.
 //!
 //! To extend the difinition of MPL grammar, let * &isin; M.
+//!
+//! #### Omit
+//! `_` is a metasymbol that always succeeds without consuming input. If `_` is in the first choice, the subtree of the first choice is omitted from the AST.
+//!
+//! ```rust ignore
+//! Omitted = _ A / f
+//! ```
+//!
+//! `Omitted` matches the same input as `A`, but the AST of `Omitted` has only an `Omit` leaf.
+//!
+//! To extend the difinition of MPL grammar, let _ &isin; M.
+//!
+//! #### And
+//! `&` is a metasymbol of the and-predicate. If `&` is the left-hand side of the first choice, the first choice succeeds without consuming input if the right-hand side succeeds.
+//!
+//! ```rust ignore
+//! And = & A / f
+//! ```
+//!
+//! `And` succeeds if `A` succeeds at the same position, but the input matched by `A` is not consumed.
+//!
+//! To extend the difinition of MPL grammar, let & &isin; M.
+//!
+//! #### Not
+//! `!` is a metasymbol of the not-predicate. If `!` is the left-hand side of the first choice, the first choice succeeds without consuming input if the right-hand side fails.
+//!
+//! ```rust ignore
+//! Not = ! A / f
+//! ```
+//!
+//! `Not` succeeds if `A` fails at the same position. For example, `NotQuote = ! '"' / f` succeeds before any input other than `"` without consuming it.
+//!
+//! To extend the difinition of MPL grammar, let ! &isin; M.
+//!
+//! #### Length-prefixed input
+//! If the output of `B` in `A = B C / D` returns `Some(len)` from `Output::len_of_next`, `C` is evaluated only over `len` of input after `B`, and must match all of it.
+//!
+//! ```rust ignore
+//! Chunk = Size Data / f
+//! Data = * () / f
+//! ```
+//!
+//! If the output of `Size` is the size of the data, `Data` matches exactly that size. This is useful for chunks of binary formats like RIFF.
 
+pub mod analysis;
 pub mod choices;
+pub mod error;
 pub mod input;
+pub mod memo;
+pub mod observer;
 pub mod output;
 pub mod parser;
 pub mod position;
+pub mod profiler;
 pub mod rules;
 pub mod span;
 pub mod symbols;
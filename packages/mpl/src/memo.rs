@@ -0,0 +1,214 @@
+//! Memo
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Memoization table of evaluation results.
+///
+/// V is (enum of) Variables.
+/// P is position.
+/// R is result of evaluation.
+///
+/// Results are memoized separately inside `!`, where failures are not recorded as expected.
+#[derive(Clone, Debug)]
+pub struct Memo<V, P, R> {
+    table: HashMap<(V, P, P, bool), R>,
+}
+
+impl<V, P, R> Default for Memo<V, P, R> {
+    fn default() -> Self {
+        Self {
+            table: HashMap::new(),
+        }
+    }
+}
+
+impl<V, P, R> Memo<V, P, R> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of memoized results.
+    pub fn len(&self) -> usize {
+        self.table.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.table.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.table.clear();
+    }
+}
+
+impl<V, P, R> Memo<V, P, R>
+where
+    V: Clone + Eq + Hash,
+    P: Clone + Eq + Hash,
+{
+    pub fn get(&self, variable: &V, pos: &P, max_pos: &P, negative_lookahead: bool) -> Option<&R> {
+        self.table.get(&(
+            variable.clone(),
+            pos.clone(),
+            max_pos.clone(),
+            negative_lookahead,
+        ))
+    }
+
+    pub fn insert(&mut self, variable: V, pos: P, max_pos: P, negative_lookahead: bool, result: R) {
+        self.table
+            .insert((variable, pos, max_pos, negative_lookahead), result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::Output;
+    use crate::observer::Tracer;
+    use crate::parser::{ParseOptions, ParseState, Parser};
+    use crate::rules::{RightRule, RightRuleKind::*, Rules};
+    use crate::span::{Len, Span, Start, StartAndLenSpan};
+    use crate::symbols::{StrTerminal, StrTerminal::*, Variable};
+    use crate::trees::AST;
+
+    #[derive(Clone, Debug, Hash, Eq, PartialEq)]
+    enum NestVariable {
+        Nest,
+        Paren,
+        Paren1,
+        Letter,
+    }
+
+    impl Variable for NestVariable {}
+
+    type NestAST = AST<NestVariable, StartAndLenSpan<u32, u32>>;
+
+    struct NestParser;
+
+    impl<'i, V, P, L, R, O> Parser<'i, str, StrTerminal<'i>, V, StartAndLenSpan<P, L>, P, R, O>
+        for NestParser
+    where
+        V: Variable,
+        P: Start<str, L>,
+        L: Len<str, P>,
+        R: Rules<StrTerminal<'i>, V>,
+        O: Output<'i, str, V, StartAndLenSpan<P, L>>,
+    {
+    }
+
+    fn nest_rules() -> HashMap<NestVariable, RightRule<StrTerminal<'static>, NestVariable>> {
+        use NestVariable::*;
+
+        let mut rules = HashMap::new();
+
+        rules.insert(
+            Nest,
+            RightRule::from_right_rule_kind((V(Paren), T(Char('x'))), V(Paren)),
+        );
+        rules.insert(
+            Paren,
+            RightRule::from_right_rule_kind((T(Char('(')), V(Paren1)), V(Letter)),
+        );
+        rules.insert(
+            Paren1,
+            RightRule::from_right_rule_kind((V(Nest), T(Char(')'))), Failure),
+        );
+        rules.insert(
+            Letter,
+            RightRule::from_right_rule_kind((T(Char('a')), Empty), Failure),
+        );
+
+        rules
+    }
+
+    /// Without memoization, `Paren` is evaluated twice for each `Nest`.
+    ///
+    /// ```
+    /// Nest = Paren 'x' / Paren
+    /// Paren = '(' Paren1 / Letter
+    /// Paren1 = Nest ')' / f
+    /// Letter = 'a' () / f
+    /// ```
+    #[test]
+    fn linear_evaluations() {
+        use NestVariable::*;
+
+        let parser = NestParser;
+        let rules = nest_rules();
+
+        let depth = 64;
+        let input = "(".repeat(depth) + "a" + &")".repeat(depth);
+        let input: &str = &input;
+        let all_of_the_span = StartAndLenSpan::<u32, u32>::from_start_len(0, input.len() as u32);
+
+        let mut memo = Memo::new();
+        let mut state = ParseState::new();
+        let result: Result<NestAST, _> = parser.eval_memoized(
+            input,
+            &all_of_the_span.lo(input),
+            &rules,
+            &Nest,
+            &all_of_the_span.hi(input),
+            &mut state,
+            &mut memo,
+        );
+
+        let ast = result.unwrap();
+        assert_eq!(ast.span, all_of_the_span);
+        assert!(memo.len() <= 4 * (input.len() + 1));
+        // Each `Nest` reuses the memoized `Paren` once, which counts as a step too.
+        assert_eq!(state.steps, memo.len() + depth + 1);
+
+        // The second choice of `Nest` reuses the AST of `Paren` without copying it.
+        let paren = &ast.as_second().unwrap().0;
+        let memoized = memo.get(&Paren, &0, &(input.len() as u32), false).unwrap();
+        assert!(paren
+            .as_internal()
+            .unwrap()
+            .equal
+            .ptr_eq(&memoized.as_ref().unwrap().as_internal().unwrap().equal));
+    }
+
+    #[test]
+    fn trace_memo_hits() {
+        let parser = NestParser;
+        let rules = nest_rules();
+        let input = "a";
+        let all_of_the_span = StartAndLenSpan::<u32, u32>::from_start_len(0, 1);
+
+        let mut tracer = Tracer::new(Vec::new());
+        let result: Result<NestAST, _> = parser.parse_memoized_with_observer(
+            input,
+            &rules,
+            &NestVariable::Nest,
+            &all_of_the_span,
+            ParseOptions::default(),
+            &mut tracer,
+        );
+        assert!(result.is_ok());
+
+        assert_eq!(
+            String::from_utf8(tracer.into_inner()).unwrap(),
+            "\
+Nest at 0
+  first
+  Paren at 0
+    first
+    '(' failed at 0
+    second
+    Letter at 0
+      first
+      'a' matched 0..1
+      () matched 1..1
+    Letter matched 0..1
+  Paren matched 0..1
+  'x' failed at 1
+  second
+  Paren matched 0..1 (memoized)
+Nest matched 0..1
+"
+        );
+    }
+}
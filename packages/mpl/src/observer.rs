@@ -0,0 +1,162 @@
+//! Observer
+
+use crate::symbols::TerminalSymbol;
+use std::fmt;
+use std::io::Write;
+
+/// Observer of evaluation.
+///
+/// Each method does nothing by default.
+///
+/// T is terminal symbols.
+/// V is (enum of) Variables.
+/// P is position.
+pub trait ParseObserver<T, V, P> {
+    /// Called when `variable` is evaluated at `pos`.
+    fn enter(&mut self, _variable: &V, _pos: &P) {}
+
+    /// Called when the first choice of `variable` is tried at `pos`.
+    fn try_first(&mut self, _variable: &V, _pos: &P) {}
+
+    /// Called when the second choice of `variable` is tried at `pos`.
+    fn try_second(&mut self, _variable: &V, _pos: &P) {}
+
+    /// Called when `terminal_symbol` matched from `pos` to `hi`.
+    fn terminal_success(&mut self, _terminal_symbol: &TerminalSymbol<T>, _pos: &P, _hi: &P) {}
+
+    /// Called when `terminal_symbol` failed at `pos`.
+    fn terminal_failure(&mut self, _terminal_symbol: &TerminalSymbol<T>, _pos: &P) {}
+
+    /// Called when evaluation of `variable` at `pos` is finished.
+    ///
+    /// `hi` is `Some` if `variable` matched.
+    fn exit(&mut self, _variable: &V, _pos: &P, _hi: Option<&P>) {}
+
+    /// Called when the memoized result of `variable` at `pos` is reused instead of evaluating it.
+    ///
+    /// `hi` is `Some` if `variable` matched.
+    fn memo_hit(&mut self, _variable: &V, _pos: &P, _hi: Option<&P>) {}
+}
+
+/// Observes nothing.
+impl<T, V, P> ParseObserver<T, V, P> for () {}
+
+impl<T, V, P, Ob> ParseObserver<T, V, P> for &mut Ob
+where
+    Ob: ParseObserver<T, V, P> + ?Sized,
+{
+    fn enter(&mut self, variable: &V, pos: &P) {
+        (**self).enter(variable, pos)
+    }
+
+    fn try_first(&mut self, variable: &V, pos: &P) {
+        (**self).try_first(variable, pos)
+    }
+
+    fn try_second(&mut self, variable: &V, pos: &P) {
+        (**self).try_second(variable, pos)
+    }
+
+    fn terminal_success(&mut self, terminal_symbol: &TerminalSymbol<T>, pos: &P, hi: &P) {
+        (**self).terminal_success(terminal_symbol, pos, hi)
+    }
+
+    fn terminal_failure(&mut self, terminal_symbol: &TerminalSymbol<T>, pos: &P) {
+        (**self).terminal_failure(terminal_symbol, pos)
+    }
+
+    fn exit(&mut self, variable: &V, pos: &P, hi: Option<&P>) {
+        (**self).exit(variable, pos, hi)
+    }
+
+    fn memo_hit(&mut self, variable: &V, pos: &P, hi: Option<&P>) {
+        (**self).memo_hit(variable, pos, hi)
+    }
+}
+
+/// Observer which writes an indented trace of evaluation.
+///
+/// ```text
+/// Open at 0
+///   first
+///   '(' matched 0..1
+///   Parentheses at 1
+///   ...
+/// Open matched 0..2
+/// ```
+///
+/// W is writer.
+pub struct Tracer<W> {
+    writer: W,
+    depth: usize,
+}
+
+impl<W> Tracer<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer, depth: 0 }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> Tracer<W> {
+    fn write_line(&mut self, args: fmt::Arguments<'_>) {
+        // Tracing is best effort.
+        let _ = writeln!(
+            self.writer,
+            "{:indent$}{}",
+            "",
+            args,
+            indent = self.depth * 2
+        );
+    }
+}
+
+impl<T, V, P, W> ParseObserver<T, V, P> for Tracer<W>
+where
+    T: fmt::Display,
+    V: fmt::Debug,
+    P: fmt::Display,
+    W: Write,
+{
+    fn enter(&mut self, variable: &V, pos: &P) {
+        self.write_line(format_args!("{:?} at {}", variable, pos));
+        self.depth += 1;
+    }
+
+    fn try_first(&mut self, _variable: &V, _pos: &P) {
+        self.write_line(format_args!("first"));
+    }
+
+    fn try_second(&mut self, _variable: &V, _pos: &P) {
+        self.write_line(format_args!("second"));
+    }
+
+    fn terminal_success(&mut self, terminal_symbol: &TerminalSymbol<T>, pos: &P, hi: &P) {
+        self.write_line(format_args!("{} matched {}..{}", terminal_symbol, pos, hi));
+    }
+
+    fn terminal_failure(&mut self, terminal_symbol: &TerminalSymbol<T>, pos: &P) {
+        self.write_line(format_args!("{} failed at {}", terminal_symbol, pos));
+    }
+
+    fn exit(&mut self, variable: &V, pos: &P, hi: Option<&P>) {
+        self.depth -= 1;
+        match hi {
+            Some(hi) => self.write_line(format_args!("{:?} matched {}..{}", variable, pos, hi)),
+            None => self.write_line(format_args!("{:?} failed at {}", variable, pos)),
+        }
+    }
+
+    fn memo_hit(&mut self, variable: &V, pos: &P, hi: Option<&P>) {
+        match hi {
+            Some(hi) => self.write_line(format_args!(
+                "{:?} matched {}..{} (memoized)",
+                variable, pos, hi
+            )),
+            None => self.write_line(format_args!("{:?} failed at {} (memoized)", variable, pos)),
+        }
+    }
+}
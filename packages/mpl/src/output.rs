@@ -5,6 +5,15 @@ use crate::trees::{AST, CST};
 /// Output types.
 pub trait Output<'i, I: ?Sized, V, S>: Sized {
     fn output_ast(input: &'i I, cst: CST<V, S, Self>) -> AST<V, S, Self>;
+
+    /// Returns the length of input after the variable which its next sibling must match exactly.
+    ///
+    /// In `A = B C / D`, if the output of `B` returns `Some(len)`, `C` is evaluated up to `len` after `B` and must end there.
+    /// Otherwise, the first choice fails.
+    /// This is for length-prefixed data like chunks of RIFF.
+    fn len_of_next(&self) -> Option<usize> {
+        None
+    }
 }
 
 impl<'i, I: ?Sized, V, S> Output<'i, I, V, S> for () {
@@ -1,12 +1,212 @@
 //! Parse
 
+use crate::error::{FarthestFailure, Limit, ParseError, ParseErrorKind};
 use crate::input::Input;
+use crate::memo::Memo;
+use crate::observer::ParseObserver;
 use crate::output::Output;
 use crate::position::Position;
-use crate::rules::Rules;
+use crate::rules::{RightRule, Rules};
 use crate::span::Span;
 use crate::symbols::{Equivalence, Metasymbol, Terminal, TerminalSymbol, Variable, E};
 use crate::trees::{AST, CST};
+use std::hash::Hash;
+use std::mem;
+use std::time::Instant;
+
+/// Result of [`Parser::parse`].
+pub type ParseResult<T, V, S, P, O = ()> = Result<AST<V, S, O>, ParseError<T, V, S, P, O>>;
+
+/// Result of [`Parser::eval`].
+pub type EvalResult<V, S, P, O = ()> = Result<AST<V, S, O>, ParseErrorKind<V, S, P, O>>;
+
+/// Options of a parse.
+///
+/// Limits bound the work of parsing untrusted input.
+/// If a limit is exceeded, the parse stops with [`ParseErrorKind::LimitExceeded`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ParseOptions {
+    /// Max depth of variables being evaluated.
+    pub max_depth: Option<usize>,
+    /// Max number of evaluated variables.
+    pub max_steps: Option<usize>,
+    /// Deadline of the parse.
+    pub deadline: Option<Instant>,
+}
+
+impl ParseOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    pub fn max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = Some(max_steps);
+        self
+    }
+
+    pub fn deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+}
+
+/// State of a parse.
+///
+/// T is terminal symbols.
+/// V is (enum of) Variables.
+/// P is position.
+/// Ob is observer.
+#[derive(Clone, Debug)]
+pub struct ParseState<T, V, P, Ob = ()> {
+    /// Variables being evaluated, from the start variable.
+    pub stack: Vec<V>,
+    pub farthest_failure: FarthestFailure<T, V, P>,
+    pub options: ParseOptions,
+    /// Number of evaluated variables.
+    pub steps: usize,
+    /// Depth of `!` being evaluated. Failures inside `!` are not recorded as farthest failures.
+    pub negative_lookahead: usize,
+    pub observer: Ob,
+}
+
+impl<T, V, P> Default for ParseState<T, V, P> {
+    fn default() -> Self {
+        Self::with_options(ParseOptions::default())
+    }
+}
+
+impl<T, V, P> ParseState<T, V, P> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_options(options: ParseOptions) -> Self {
+        Self {
+            stack: Vec::new(),
+            farthest_failure: FarthestFailure::default(),
+            options,
+            steps: 0,
+            negative_lookahead: 0,
+            observer: (),
+        }
+    }
+}
+
+impl<T, V, P, Ob> ParseState<T, V, P, Ob> {
+    /// Returns the state observed by `observer` with the same options.
+    pub fn with_observer<Ob2>(self, observer: Ob2) -> ParseState<T, V, P, Ob2> {
+        ParseState {
+            stack: self.stack,
+            farthest_failure: self.farthest_failure,
+            options: self.options,
+            steps: self.steps,
+            negative_lookahead: self.negative_lookahead,
+            observer,
+        }
+    }
+
+    /// Counts a step of evaluation, such as evaluating a variable or reusing its memoized result.
+    ///
+    /// Returns the exceeded limit instead if any.
+    pub fn step(&mut self) -> Result<(), Limit> {
+        if let Some(max_steps) = self.options.max_steps {
+            if self.steps >= max_steps {
+                return Err(Limit::Steps(max_steps));
+            }
+        }
+        if let Some(deadline) = self.options.deadline {
+            if Instant::now() >= deadline {
+                return Err(Limit::Deadline);
+            }
+        }
+
+        self.steps += 1;
+        Ok(())
+    }
+
+    /// Pushes `variable` evaluated at `pos` to the stack as a step of evaluation.
+    ///
+    /// Returns the exceeded limit instead if any.
+    pub fn push(&mut self, variable: V, pos: &P) -> Result<(), Limit>
+    where
+        Ob: ParseObserver<T, V, P>,
+    {
+        if let Some(max_depth) = self.options.max_depth {
+            if self.stack.len() >= max_depth {
+                return Err(Limit::Depth(max_depth));
+            }
+        }
+        self.step()?;
+
+        self.observer.enter(&variable, pos);
+        self.stack.push(variable);
+        Ok(())
+    }
+
+    /// Pops the variable evaluated at `pos` into `result` from the stack.
+    pub fn pop<I, S, O>(&mut self, input: &I, pos: &P, result: &EvalResult<V, S, P, O>)
+    where
+        I: Input + ?Sized,
+        S: Span<I, P>,
+        P: Position,
+        Ob: ParseObserver<T, V, P>,
+    {
+        if let Some(variable) = self.stack.pop() {
+            let hi = result.as_ref().ok().map(|ast| ast.span.hi(input));
+            self.observer.exit(&variable, pos, hi.as_ref());
+        }
+    }
+
+    /// Converts the result of evaluating the start variable into the result of a parse.
+    pub fn into_parse_result<I, S, O>(
+        self,
+        input: &I,
+        result: EvalResult<V, S, P, O>,
+        all_of_the_span: &S,
+    ) -> ParseResult<T, V, S, P, O>
+    where
+        I: Input + ?Sized,
+        S: Span<I, P>,
+        P: Position,
+    {
+        let kind = match result {
+            Ok(ast) if &ast.span == all_of_the_span => return Ok(ast),
+            Ok(ast) => {
+                let hi = ast.span.hi(input);
+                ParseErrorKind::PartialMatch { ast, hi }
+            }
+            Err(kind) => kind,
+        };
+
+        Err(ParseError::new(kind, self.farthest_failure))
+    }
+}
+
+/// Frame of [`Parser::eval_iterative`] evaluating `A = B C / D`.
+struct Frame<'r, T, V, P, A> {
+    variable: V,
+    right_rule: &'r RightRule<T, V>,
+    pos: P,
+    max_pos: P,
+    step: Step<A, P>,
+}
+
+/// Which of `B`, `C` and `D` a [`Frame`] is evaluating.
+enum Step<A, P> {
+    /// `B`
+    Lhs,
+    /// `C` with the AST of `B`, and the position where `C` must end if any.
+    Rhs(A, Option<P>),
+    /// `C` as the operand of `B`, which is `&` or `!`.
+    Lookahead(Metasymbol),
+    /// `D`
+    Second,
+}
 
 /// Types that can be parsed.
 ///
@@ -17,7 +217,6 @@ use crate::trees::{AST, CST};
 /// P is position.
 /// R is Rules.
 /// O is output type.
-// TODO: Create Error types
 pub trait Parser<'i, I, T, V, S, P, R, O = ()>
 where
     I: Input + ?Sized,
@@ -39,20 +238,221 @@ where
         rules: &R,
         start_variable: &V,
         all_of_the_span: &S,
-    ) -> Result<AST<V, S, O>, AST<V, S, O>> {
-        let ast = self.eval(
+    ) -> ParseResult<T, V, S, P, O>
+    where
+        T: Clone + PartialEq,
+    {
+        self.parse_with_options(
+            input,
+            rules,
+            start_variable,
+            all_of_the_span,
+            ParseOptions::default(),
+        )
+    }
+
+    /// Minimal parse with limits of `options`.
+    fn parse_with_options(
+        &self,
+        input: &'i I,
+        rules: &R,
+        start_variable: &V,
+        all_of_the_span: &S,
+        options: ParseOptions,
+    ) -> ParseResult<T, V, S, P, O>
+    where
+        T: Clone + PartialEq,
+    {
+        self.parse_with_observer(input, rules, start_variable, all_of_the_span, options, ())
+    }
+
+    /// Minimal parse with limits of `options` observed by `observer`.
+    ///
+    /// Pass `&mut observer` to inspect it after the parse.
+    fn parse_with_observer<Ob>(
+        &self,
+        input: &'i I,
+        rules: &R,
+        start_variable: &V,
+        all_of_the_span: &S,
+        options: ParseOptions,
+        observer: Ob,
+    ) -> ParseResult<T, V, S, P, O>
+    where
+        T: Clone + PartialEq,
+        Ob: ParseObserver<T, V, P>,
+    {
+        let mut state = ParseState::with_options(options).with_observer(observer);
+        let result = self.eval(
             input,
             &all_of_the_span.lo(input),
             rules,
             start_variable,
             &all_of_the_span.hi(input),
-        )?;
+            &mut state,
+        );
 
-        if &ast.span == all_of_the_span {
-            Ok(ast)
-        } else {
-            Err(ast)
-        }
+        state.into_parse_result(input, result, all_of_the_span)
+    }
+
+    /// Minimal parse with memoization.
+    ///
+    /// Same as [`Parser::parse`], but the result of each `(variable, pos, max_pos)` is memoized for the duration of the parse.
+    /// Each variable is evaluated at most once per position and `max_pos`, and a memoized AST is reused without copying its subtrees since they are shared with `Arc`.
+    /// So unless lengths of [`Output::len_of_next`] add positions to end at, parsing takes linear time in the length of the input like packrat parsers.
+    fn parse_memoized(
+        &self,
+        input: &'i I,
+        rules: &R,
+        start_variable: &V,
+        all_of_the_span: &S,
+    ) -> ParseResult<T, V, S, P, O>
+    where
+        T: Clone + PartialEq,
+        V: Eq + Hash,
+        P: Eq + Hash,
+        O: Clone,
+    {
+        self.parse_memoized_with_options(
+            input,
+            rules,
+            start_variable,
+            all_of_the_span,
+            ParseOptions::default(),
+        )
+    }
+
+    /// Minimal parse with memoization and limits of `options`.
+    ///
+    /// Reusing a memoized result counts as a step.
+    fn parse_memoized_with_options(
+        &self,
+        input: &'i I,
+        rules: &R,
+        start_variable: &V,
+        all_of_the_span: &S,
+        options: ParseOptions,
+    ) -> ParseResult<T, V, S, P, O>
+    where
+        T: Clone + PartialEq,
+        V: Eq + Hash,
+        P: Eq + Hash,
+        O: Clone,
+    {
+        self.parse_memoized_with_observer(
+            input,
+            rules,
+            start_variable,
+            all_of_the_span,
+            options,
+            (),
+        )
+    }
+
+    /// Minimal parse with memoization and limits of `options` observed by `observer`.
+    ///
+    /// Reusing a memoized result is observed by [`ParseObserver::memo_hit`] instead of evaluating the variable again.
+    #[allow(clippy::too_many_arguments)]
+    fn parse_memoized_with_observer<Ob>(
+        &self,
+        input: &'i I,
+        rules: &R,
+        start_variable: &V,
+        all_of_the_span: &S,
+        options: ParseOptions,
+        observer: Ob,
+    ) -> ParseResult<T, V, S, P, O>
+    where
+        T: Clone + PartialEq,
+        V: Eq + Hash,
+        P: Eq + Hash,
+        O: Clone,
+        Ob: ParseObserver<T, V, P>,
+    {
+        let mut state = ParseState::with_options(options).with_observer(observer);
+        let mut memo = Memo::new();
+        let result = self.eval_memoized(
+            input,
+            &all_of_the_span.lo(input),
+            rules,
+            start_variable,
+            &all_of_the_span.hi(input),
+            &mut state,
+            &mut memo,
+        );
+
+        state.into_parse_result(input, result, all_of_the_span)
+    }
+
+    /// Minimal parse without native recursion.
+    ///
+    /// Same as [`Parser::parse`], but variables are evaluated with a stack on the heap, so deeply right-recursive rules like `Lines = Line Lines / ()` don't overflow the thread stack.
+    fn parse_iterative(
+        &self,
+        input: &'i I,
+        rules: &R,
+        start_variable: &V,
+        all_of_the_span: &S,
+    ) -> ParseResult<T, V, S, P, O>
+    where
+        T: Clone + PartialEq,
+    {
+        self.parse_iterative_with_options(
+            input,
+            rules,
+            start_variable,
+            all_of_the_span,
+            ParseOptions::default(),
+        )
+    }
+
+    /// Minimal parse without native recursion with limits of `options`.
+    fn parse_iterative_with_options(
+        &self,
+        input: &'i I,
+        rules: &R,
+        start_variable: &V,
+        all_of_the_span: &S,
+        options: ParseOptions,
+    ) -> ParseResult<T, V, S, P, O>
+    where
+        T: Clone + PartialEq,
+    {
+        self.parse_iterative_with_observer(
+            input,
+            rules,
+            start_variable,
+            all_of_the_span,
+            options,
+            (),
+        )
+    }
+
+    /// Minimal parse without native recursion with limits of `options` observed by `observer`.
+    fn parse_iterative_with_observer<Ob>(
+        &self,
+        input: &'i I,
+        rules: &R,
+        start_variable: &V,
+        all_of_the_span: &S,
+        options: ParseOptions,
+        observer: Ob,
+    ) -> ParseResult<T, V, S, P, O>
+    where
+        T: Clone + PartialEq,
+        Ob: ParseObserver<T, V, P>,
+    {
+        let mut state = ParseState::with_options(options).with_observer(observer);
+        let result = self.eval_iterative(
+            input,
+            &all_of_the_span.lo(input),
+            rules,
+            start_variable,
+            &all_of_the_span.hi(input),
+            &mut state,
+        );
+
+        state.into_parse_result(input, result, all_of_the_span)
     }
 
     fn to_empty_ast(&self, input: &'i I, pos: P) -> Result<AST<V, S, O>, AST<V, S, O>> {
@@ -62,6 +462,13 @@ where
         ))
     }
 
+    fn to_omit_ast(&self, input: &'i I, pos: P) -> Result<AST<V, S, O>, AST<V, S, O>> {
+        Ok(AST::from_leaf(
+            Metasymbol::Omit.into(),
+            Span::from_lo_hi(pos.clone(), pos, input),
+        ))
+    }
+
     fn to_failure_ast(&self, input: &'i I, pos: P) -> Result<AST<V, S, O>, AST<V, S, O>> {
         Err(AST::from_leaf(
             Metasymbol::Failure.into(),
@@ -69,6 +476,20 @@ where
         ))
     }
 
+    fn to_and_ast(&self, input: &'i I, pos: P) -> Result<AST<V, S, O>, AST<V, S, O>> {
+        Ok(AST::from_leaf(
+            Metasymbol::And.into(),
+            Span::from_lo_hi(pos.clone(), pos, input),
+        ))
+    }
+
+    fn to_not_ast(&self, input: &'i I, pos: P) -> Result<AST<V, S, O>, AST<V, S, O>> {
+        Err(AST::from_leaf(
+            Metasymbol::Not.into(),
+            Span::from_lo_hi(pos.clone(), pos, input),
+        ))
+    }
+
     // TODO: Decide return Any or Failure
     fn to_any_ast(
         &self,
@@ -77,7 +498,16 @@ where
         max_pos: &P,
         n: usize,
     ) -> Result<AST<V, S, O>, AST<V, S, O>> {
-        let span_with_len_added = S::from_lo_len(pos, n, input);
+        // Not enough units such as chars of `str` left.
+        let span_with_len_added = match S::from_lo_units(pos.clone(), n, input) {
+            Some(span) => span,
+            None => {
+                return Err(AST::from_leaf(
+                    Metasymbol::Any(n).into(),
+                    Span::from_lo_hi(pos.clone(), pos, input),
+                ))
+            }
+        };
         let hi = span_with_len_added.hi(input);
         let ast = AST::from_leaf(Metasymbol::Any(n).into(), span_with_len_added);
         if &hi <= max_pos {
@@ -108,75 +538,533 @@ where
                 Metasymbol::Failure => self.to_failure_ast(input, pos),
                 Metasymbol::Any(n) => self.to_any_ast(input, pos, max_pos, *n),
                 Metasymbol::All => self.to_all_ast(input, pos, max_pos.clone()),
-                Metasymbol::Omit => unimplemented!(),
+                Metasymbol::Omit => self.to_omit_ast(input, pos),
+                // Without an operand, `&` matches nothing and `!` everything.
+                Metasymbol::And => self.to_and_ast(input, pos),
+                Metasymbol::Not => self.to_not_ast(input, pos),
             },
         }
     }
 
-    fn eval(
+    /// Evaluates a terminal symbol of a right rule and records its failure in `state`.
+    fn eval_e_terminal_symbol<Ob>(
+        &self,
+        input: &'i I,
+        terminal_symbol: &TerminalSymbol<T>,
+        pos: P,
+        max_pos: &P,
+        state: &mut ParseState<T, V, P, Ob>,
+    ) -> EvalResult<V, S, P, O>
+    where
+        T: Clone + PartialEq,
+        Ob: ParseObserver<T, V, P>,
+    {
+        self.eval_terminal_symbol(input, terminal_symbol, pos.clone(), max_pos)
+            .inspect(|ast| {
+                state
+                    .observer
+                    .terminal_success(terminal_symbol, &pos, &ast.span.hi(input));
+            })
+            .map_err(|ast| {
+                state.observer.terminal_failure(terminal_symbol, &pos);
+                // `f` and `!` are not expected, nor is anything inside `!`.
+                if !matches!(
+                    terminal_symbol,
+                    TerminalSymbol::Metasymbol(Metasymbol::Failure | Metasymbol::Not)
+                ) && state.negative_lookahead == 0
+                {
+                    state
+                        .farthest_failure
+                        .record(terminal_symbol, pos, &state.stack);
+                }
+                ParseErrorKind::Failure(ast)
+            })
+    }
+
+    fn eval<Ob>(
         &self,
         input: &'i I,
         pos: &P,
         rules: &R,
         variable: &V,
         max_pos: &P,
-    ) -> Result<AST<V, S, O>, AST<V, S, O>> {
-        let right_rule = rules.get(variable).expect("right_rule from a variable");
+        state: &mut ParseState<T, V, P, Ob>,
+    ) -> EvalResult<V, S, P, O>
+    where
+        T: Clone + PartialEq,
+        Ob: ParseObserver<T, V, P>,
+    {
+        let right_rule = rules
+            .get(variable)
+            .ok_or_else(|| ParseErrorKind::MissingRule(variable.clone()))?;
 
-        // First choice
-        // left-hand side of first choice
-        let left_ast: Result<AST<V, S, O>, AST<V, S, O>> = match &right_rule.first.lhs {
-            E::T(terminal_symbol) => {
-                self.eval_terminal_symbol(input, terminal_symbol, pos.clone(), max_pos)
-            }
-            E::V(lhs_of_fc_v) => self.eval(input, pos, rules, lhs_of_fc_v, max_pos),
-        };
-
-        if let Ok(left_ast) = left_ast {
-            // right-hand side of first choice
-            let right_ast: Result<AST<V, S, O>, AST<V, S, O>> = match &right_rule.first.rhs {
-                E::T(terminal_symbol) => self.eval_terminal_symbol(
-                    input,
-                    terminal_symbol,
-                    left_ast.span.hi(input),
-                    max_pos,
-                ),
-                E::V(rhs_of_fc_v) => {
-                    self.eval(input, &left_ast.span.hi(input), rules, rhs_of_fc_v, max_pos)
+        state
+            .push(variable.clone(), pos)
+            .map_err(ParseErrorKind::LimitExceeded)?;
+        let result = self.eval_right_rule(
+            input,
+            pos,
+            variable,
+            right_rule,
+            max_pos,
+            state,
+            |e, pos, max_pos, state| match e {
+                E::T(terminal_symbol) => {
+                    self.eval_e_terminal_symbol(input, terminal_symbol, pos, max_pos, state)
                 }
-            };
+                E::V(v) => self.eval(input, &pos, rules, v, max_pos, state),
+            },
+        );
+        state.pop(input, pos, &result);
+
+        result
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn eval_memoized<Ob>(
+        &self,
+        input: &'i I,
+        pos: &P,
+        rules: &R,
+        variable: &V,
+        max_pos: &P,
+        state: &mut ParseState<T, V, P, Ob>,
+        memo: &mut Memo<V, P, EvalResult<V, S, P, O>>,
+    ) -> EvalResult<V, S, P, O>
+    where
+        T: Clone + PartialEq,
+        Ob: ParseObserver<T, V, P>,
+        V: Eq + Hash,
+        P: Eq + Hash,
+        O: Clone,
+    {
+        let negative_lookahead = state.negative_lookahead != 0;
+        if let Some(result) = memo.get(variable, pos, max_pos, negative_lookahead) {
+            state.step().map_err(ParseErrorKind::LimitExceeded)?;
+            let hi = result.as_ref().ok().map(|ast| ast.span.hi(input));
+            state.observer.memo_hit(variable, pos, hi.as_ref());
+            return result.clone();
+        }
 
-            if let Ok(right_ast) = right_ast {
-                let merged_span = Span::merge_lhs_and_rhs(&left_ast.span, &right_ast.span, input);
+        let right_rule = rules
+            .get(variable)
+            .ok_or_else(|| ParseErrorKind::MissingRule(variable.clone()))?;
 
-                let variable_and_choice =
-                    Equivalence::new(variable.clone(), (left_ast, right_ast).into());
+        state
+            .push(variable.clone(), pos)
+            .map_err(ParseErrorKind::LimitExceeded)?;
+        let result = self.eval_right_rule(
+            input,
+            pos,
+            variable,
+            right_rule,
+            max_pos,
+            state,
+            |e, pos, max_pos, state| match e {
+                E::T(terminal_symbol) => {
+                    self.eval_e_terminal_symbol(input, terminal_symbol, pos, max_pos, state)
+                }
+                E::V(v) => self.eval_memoized(input, &pos, rules, v, max_pos, state, memo),
+            },
+        );
+        state.pop(input, pos, &result);
 
-                let cst = CST::new(variable_and_choice, merged_span);
+        memo.insert(
+            variable.clone(),
+            pos.clone(),
+            max_pos.clone(),
+            negative_lookahead,
+            result.clone(),
+        );
 
-                let output_ast = O::output_ast(input, cst);
+        result
+    }
 
-                return Ok(output_ast);
+    /// Evaluates `A = B C / D`.
+    ///
+    /// `eval_e` evaluates each of `B`, `C` and `D` at the given position up to the given `max_pos`.
+    /// Errors other than [`ParseErrorKind::Failure`] are returned without trying the second choice.
+    #[allow(clippy::too_many_arguments)]
+    fn eval_right_rule<Ob, F>(
+        &self,
+        input: &'i I,
+        pos: &P,
+        variable: &V,
+        right_rule: &RightRule<T, V>,
+        max_pos: &P,
+        state: &mut ParseState<T, V, P, Ob>,
+        mut eval_e: F,
+    ) -> EvalResult<V, S, P, O>
+    where
+        Ob: ParseObserver<T, V, P>,
+        F: FnMut(&E<T, V>, P, &P, &mut ParseState<T, V, P, Ob>) -> EvalResult<V, S, P, O>,
+    {
+        // First choice
+        state.observer.try_first(variable, pos);
+        if let Some(lookahead) = lookahead(&right_rule.first.lhs) {
+            // right-hand side of first choice without consuming input
+            if lookahead == Metasymbol::Not {
+                state.negative_lookahead += 1;
+            }
+            let result = eval_e(&right_rule.first.rhs, pos.clone(), max_pos, state);
+            if lookahead == Metasymbol::Not {
+                state.negative_lookahead -= 1;
+            }
+            if lookahead_matched(lookahead, result)? {
+                return Ok(lookahead_ast(input, variable, right_rule, lookahead, pos));
+            }
+        } else {
+            // left-hand side of first choice
+            match eval_e(&right_rule.first.lhs, pos.clone(), max_pos, state) {
+                Ok(left_ast) => {
+                    // right-hand side of first choice
+                    // the first choice fails if the length of the right-hand side doesn't fit
+                    if let Some((rhs_max_pos, end)) = rhs_max_pos(input, &left_ast, max_pos) {
+                        let rhs_pos = left_ast.span.hi(input);
+                        match eval_e(&right_rule.first.rhs, rhs_pos, &rhs_max_pos, state) {
+                            Ok(right_ast) if ends_at(input, &right_ast, &end) => {
+                                return Ok(first_choice_ast(
+                                    input, variable, right_rule, left_ast, right_ast,
+                                ));
+                            }
+                            Ok(_) | Err(ParseErrorKind::Failure(_)) => {}
+                            Err(e) => return Err(e),
+                        }
+                    }
+                }
+                Err(ParseErrorKind::Failure(_)) => {}
+                Err(e) => return Err(e),
             }
         }
 
         // Second choice
+        state.observer.try_second(variable, pos);
         match &right_rule.second.0 {
-            E::T(terminal_symbol) => {
-                self.eval_terminal_symbol(input, terminal_symbol, pos.clone(), max_pos)
+            E::T(_) => eval_e(&right_rule.second.0, pos.clone(), max_pos, state),
+            E::V(_) => {
+                let ast = eval_e(&right_rule.second.0, pos.clone(), max_pos, state)?;
+
+                Ok(second_choice_ast(input, variable, ast))
             }
-            E::V(sc_v) => {
-                let ast = self.eval(input, pos, rules, sc_v, max_pos)?;
-                let span = ast.span.clone();
+        }
+    }
 
-                let variable_and_choice = Equivalence::new(variable.clone(), ast.into());
+    /// Same as [`Parser::eval`], but uses a stack on the heap instead of native recursion.
+    fn eval_iterative<Ob>(
+        &self,
+        input: &'i I,
+        pos: &P,
+        rules: &R,
+        variable: &V,
+        max_pos: &P,
+        state: &mut ParseState<T, V, P, Ob>,
+    ) -> EvalResult<V, S, P, O>
+    where
+        T: Clone + PartialEq,
+        Ob: ParseObserver<T, V, P>,
+    {
+        let right_rule = rules
+            .get(variable)
+            .ok_or_else(|| ParseErrorKind::MissingRule(variable.clone()))?;
 
-                let cst = CST::new(variable_and_choice, span);
+        state
+            .push(variable.clone(), pos)
+            .map_err(ParseErrorKind::LimitExceeded)?;
+        let mut frames = vec![Frame {
+            variable: variable.clone(),
+            right_rule,
+            pos: pos.clone(),
+            max_pos: max_pos.clone(),
+            step: Step::Lhs,
+        }];
+        // Result of the last evaluated `E`.
+        let mut result: Option<EvalResult<V, S, P, O>> = None;
 
-                let output_ast = O::output_ast(input, cst);
+        while let Some(frame) = frames.last_mut() {
+            let right_rule = frame.right_rule;
+            let next = match (mem::replace(&mut frame.step, Step::Second), result.take()) {
+                // First choice
+                (Step::Lhs, None) => {
+                    state.observer.try_first(&frame.variable, &frame.pos);
+                    match lookahead(&right_rule.first.lhs) {
+                        Some(lookahead) => {
+                            if lookahead == Metasymbol::Not {
+                                state.negative_lookahead += 1;
+                            }
+                            frame.step = Step::Lookahead(lookahead);
+                            Some((
+                                &right_rule.first.rhs,
+                                frame.pos.clone(),
+                                frame.max_pos.clone(),
+                            ))
+                        }
+                        None => {
+                            frame.step = Step::Lhs;
+                            Some((
+                                &right_rule.first.lhs,
+                                frame.pos.clone(),
+                                frame.max_pos.clone(),
+                            ))
+                        }
+                    }
+                }
+                (Step::Lookahead(lookahead), Some(r)) => {
+                    if lookahead == Metasymbol::Not {
+                        state.negative_lookahead -= 1;
+                    }
+                    match lookahead_matched(lookahead, r) {
+                        Ok(true) => {
+                            result = Some(Ok(lookahead_ast(
+                                input,
+                                &frame.variable,
+                                right_rule,
+                                lookahead,
+                                &frame.pos,
+                            )));
+                            None
+                        }
+                        Ok(false) => {
+                            state.observer.try_second(&frame.variable, &frame.pos);
+                            Some((
+                                &right_rule.second.0,
+                                frame.pos.clone(),
+                                frame.max_pos.clone(),
+                            ))
+                        }
+                        Err(e) => {
+                            result = Some(Err(e));
+                            None
+                        }
+                    }
+                }
+                (Step::Lhs, Some(Ok(left_ast))) => {
+                    match rhs_max_pos(input, &left_ast, &frame.max_pos) {
+                        Some((rhs_max_pos, end)) => {
+                            let hi = left_ast.span.hi(input);
+                            frame.step = Step::Rhs(left_ast, end);
+                            Some((&right_rule.first.rhs, hi, rhs_max_pos))
+                        }
+                        // the length of the right-hand side doesn't fit
+                        None => {
+                            state.observer.try_second(&frame.variable, &frame.pos);
+                            Some((
+                                &right_rule.second.0,
+                                frame.pos.clone(),
+                                frame.max_pos.clone(),
+                            ))
+                        }
+                    }
+                }
+                (Step::Rhs(left_ast, end), Some(Ok(right_ast)))
+                    if ends_at(input, &right_ast, &end) =>
+                {
+                    result = Some(Ok(first_choice_ast(
+                        input,
+                        &frame.variable,
+                        right_rule,
+                        left_ast,
+                        right_ast,
+                    )));
+                    None
+                }
+                // Second choice
+                (Step::Lhs | Step::Rhs(..), Some(Err(ParseErrorKind::Failure(_))))
+                | (Step::Rhs(..), Some(Ok(_))) => {
+                    state.observer.try_second(&frame.variable, &frame.pos);
+                    Some((
+                        &right_rule.second.0,
+                        frame.pos.clone(),
+                        frame.max_pos.clone(),
+                    ))
+                }
+                (Step::Second, Some(Ok(ast))) if matches!(right_rule.second.0, E::V(_)) => {
+                    result = Some(Ok(second_choice_ast(input, &frame.variable, ast)));
+                    None
+                }
+                (_, Some(r)) => {
+                    result = Some(r);
+                    None
+                }
+                (_, None) => unreachable!(),
+            };
 
-                Ok(output_ast)
+            match next {
+                Some((E::T(terminal_symbol), pos, max_pos)) => {
+                    result = Some(self.eval_e_terminal_symbol(
+                        input,
+                        terminal_symbol,
+                        pos,
+                        &max_pos,
+                        state,
+                    ));
+                }
+                Some((E::V(v), pos, max_pos)) => match rules.get(v) {
+                    Some(right_rule) => match state.push(v.clone(), &pos) {
+                        Ok(()) => frames.push(Frame {
+                            variable: v.clone(),
+                            right_rule,
+                            pos,
+                            max_pos,
+                            step: Step::Lhs,
+                        }),
+                        Err(limit) => result = Some(Err(ParseErrorKind::LimitExceeded(limit))),
+                    },
+                    None => result = Some(Err(ParseErrorKind::MissingRule(v.clone()))),
+                },
+                None => {
+                    if let (Some(frame), Some(result)) = (frames.pop(), &result) {
+                        state.pop(input, &frame.pos, result);
+                    }
+                }
             }
         }
+
+        result.expect("the start variable has been evaluated")
     }
 }
+
+/// Returns the `max_pos` of `C` in `A = B C / D` given the AST of `B`.
+///
+/// If the output of `B` has [`Output::len_of_next`], `C` is limited to the length after `B`, and the position where `C` must end is also returned.
+/// Returns `None` if the length exceeds `max_pos`.
+fn rhs_max_pos<'i, I, V, S, P, O>(
+    input: &'i I,
+    left_ast: &AST<V, S, O>,
+    max_pos: &P,
+) -> Option<(P, Option<P>)>
+where
+    I: Input + ?Sized,
+    S: Span<I, P>,
+    P: Position,
+    O: Output<'i, I, V, S>,
+{
+    let len = left_ast
+        .as_internal()
+        .and_then(|internal| internal.value.1.as_ref())
+        .and_then(O::len_of_next);
+    match len {
+        Some(len) => {
+            let end =
+                S::checked_from_lo_len(left_ast.span.hi(input), len, max_pos, input)?.hi(input);
+            Some((end.clone(), Some(end)))
+        }
+        None => Some((max_pos.clone(), None)),
+    }
+}
+
+/// Returns true if `ast` ends at `end`, or `end` is `None`.
+fn ends_at<I, V, S, P, O>(input: &I, ast: &AST<V, S, O>, end: &Option<P>) -> bool
+where
+    I: Input + ?Sized,
+    S: Span<I, P>,
+    P: Position,
+{
+    match end {
+        Some(end) => &ast.span.hi(input) == end,
+        None => true,
+    }
+}
+
+/// Returns `&` or `!` if `e` is one of them.
+fn lookahead<T, V>(e: &E<T, V>) -> Option<Metasymbol> {
+    match e {
+        E::T(TerminalSymbol::Metasymbol(metasymbol @ (Metasymbol::And | Metasymbol::Not))) => {
+            Some(*metasymbol)
+        }
+        _ => None,
+    }
+}
+
+/// Returns true if `&C` or `!C` matched given the result of `C`.
+///
+/// Errors other than [`ParseErrorKind::Failure`] are returned.
+fn lookahead_matched<V, S, P, O>(
+    lookahead: Metasymbol,
+    result: EvalResult<V, S, P, O>,
+) -> Result<bool, ParseErrorKind<V, S, P, O>> {
+    let matched = match result {
+        Ok(_) => true,
+        Err(ParseErrorKind::Failure(_)) => false,
+        Err(e) => return Err(e),
+    };
+
+    Ok(matched == (lookahead == Metasymbol::And))
+}
+
+/// Converts the first choice `&C` or `!C` of `A = & C / D` or `A = ! C / D` into AST.
+///
+/// Both sides are leaves at `pos` since lookahead consumes no input.
+fn lookahead_ast<'i, I, T, V, S, P, O>(
+    input: &'i I,
+    variable: &V,
+    right_rule: &RightRule<T, V>,
+    lookahead: Metasymbol,
+    pos: &P,
+) -> AST<V, S, O>
+where
+    I: Input + ?Sized,
+    V: Clone,
+    S: Span<I, P>,
+    P: Position,
+    O: Output<'i, I, V, S>,
+{
+    let left_ast = AST::from_leaf(
+        lookahead.into(),
+        Span::from_lo_hi(pos.clone(), pos.clone(), input),
+    );
+    let right_ast = AST::from_leaf(
+        Metasymbol::Empty.into(),
+        Span::from_lo_hi(pos.clone(), pos.clone(), input),
+    );
+
+    first_choice_ast(input, variable, right_rule, left_ast, right_ast)
+}
+
+/// Converts the first choice `B C` of `A = B C / D` into AST.
+///
+/// The subtree is omitted if `B` or `C` is `_`.
+fn first_choice_ast<'i, I, T, V, S, P, O>(
+    input: &'i I,
+    variable: &V,
+    right_rule: &RightRule<T, V>,
+    left_ast: AST<V, S, O>,
+    right_ast: AST<V, S, O>,
+) -> AST<V, S, O>
+where
+    I: Input + ?Sized,
+    V: Clone,
+    S: Span<I, P>,
+    P: Position,
+    O: Output<'i, I, V, S>,
+{
+    let merged_span = Span::merge_lhs_and_rhs(&left_ast.span, &right_ast.span, input);
+
+    let variable_and_choice = Equivalence::new(variable.clone(), (left_ast, right_ast).into());
+
+    let mut cst = CST::new(variable_and_choice, merged_span);
+    if [&right_rule.first.lhs, &right_rule.first.rhs]
+        .iter()
+        .any(|e| matches!(e, E::T(TerminalSymbol::Metasymbol(Metasymbol::Omit))))
+    {
+        cst = cst.into_omit();
+    }
+
+    O::output_ast(input, cst)
+}
+
+/// Converts the second choice `D` of `A = B C / D` into AST.
+fn second_choice_ast<'i, I, V, S, O>(input: &'i I, variable: &V, ast: AST<V, S, O>) -> AST<V, S, O>
+where
+    I: ?Sized,
+    V: Clone,
+    S: Clone,
+    O: Output<'i, I, V, S>,
+{
+    let span = ast.span.clone();
+
+    let variable_and_choice = Equivalence::new(variable.clone(), ast.into());
+
+    let cst = CST::new(variable_and_choice, span);
+
+    O::output_ast(input, cst)
+}
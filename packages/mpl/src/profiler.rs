@@ -0,0 +1,243 @@
+//! Profiler
+
+use crate::input::Input;
+use crate::observer::ParseObserver;
+use crate::span::Start;
+use crate::symbols::TerminalSymbol;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+/// Statistics of a variable.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct VariableProfile {
+    /// Number of evaluations.
+    pub invocations: usize,
+    /// Number of times the memoized result was reused instead of evaluating.
+    pub memo_hits: usize,
+    pub successes: usize,
+    pub failures: usize,
+    /// Number of times the first choice failed and the second choice was tried.
+    pub backtracks: usize,
+    /// Total length consumed by successes.
+    pub consumed: u64,
+    /// Total length scanned by first choices before the second choices were tried.
+    pub rescanned: u64,
+}
+
+impl VariableProfile {
+    /// Returns the cost of the variable, which is the number of invocations and memo hits plus the length rescanned by backtracking.
+    ///
+    /// A variable evaluated once per position costs about the length of the input, and backtracking over long first choices adds to it.
+    pub fn cost(&self) -> u64 {
+        (self.invocations + self.memo_hits) as u64 + self.rescanned
+    }
+}
+
+/// Observer which counts statistics per variable.
+///
+/// Lengths are measured in offsets of positions in the input by [`Start::into_usize`].
+///
+/// I is Input.
+/// V is (enum of) Variables.
+/// L is length of spans, whose positions implement `Start<I, L>`.
+#[derive(Debug)]
+pub struct Profiler<'i, I: ?Sized, V, L> {
+    input: &'i I,
+    profiles: HashMap<V, (usize, VariableProfile)>,
+    /// Offset and the farthest offset scanned of each variable being evaluated.
+    scans: Vec<(usize, usize)>,
+    len: PhantomData<L>,
+}
+
+impl<'i, I, V, L> Profiler<'i, I, V, L>
+where
+    I: Input + ?Sized,
+    V: Clone + Eq + Hash,
+{
+    pub fn new(input: &'i I) -> Self {
+        Self {
+            input,
+            profiles: HashMap::new(),
+            scans: Vec::new(),
+            len: PhantomData,
+        }
+    }
+
+    pub fn get(&self, variable: &V) -> Option<&VariableProfile> {
+        self.profiles.get(variable).map(|(_, profile)| profile)
+    }
+
+    /// Returns profiles sorted by [`VariableProfile::cost`] in descending order.
+    ///
+    /// Ties are in order of first evaluation.
+    pub fn report(&self) -> Vec<(V, VariableProfile)> {
+        let mut profiles: Vec<_> = self.profiles.iter().collect();
+        profiles.sort_by(|(_, (a_order, a)), (_, (b_order, b))| {
+            b.cost().cmp(&a.cost()).then(a_order.cmp(b_order))
+        });
+
+        profiles
+            .into_iter()
+            .map(|(variable, (_, profile))| (variable.clone(), *profile))
+            .collect()
+    }
+
+    fn profile_mut(&mut self, variable: &V) -> &mut VariableProfile {
+        let order = self.profiles.len();
+        &mut self
+            .profiles
+            .entry(variable.clone())
+            .or_insert_with(|| (order, VariableProfile::default()))
+            .1
+    }
+
+    fn offset<P: Start<I, L>>(&self, pos: &P) -> usize {
+        P::into_usize(pos.clone(), self.input)
+    }
+
+    /// Records that the variable being evaluated has scanned up to `pos`.
+    fn scan<P: Start<I, L>>(&mut self, pos: &P) {
+        let offset = self.offset(pos);
+        if let Some((_, farthest)) = self.scans.last_mut() {
+            *farthest = (*farthest).max(offset);
+        }
+    }
+}
+
+impl<'i, T, I, V, P, L> ParseObserver<T, V, P> for Profiler<'i, I, V, L>
+where
+    I: Input + ?Sized,
+    V: Clone + Eq + Hash,
+    P: Start<I, L>,
+{
+    fn enter(&mut self, variable: &V, pos: &P) {
+        self.profile_mut(variable).invocations += 1;
+        let offset = self.offset(pos);
+        self.scans.push((offset, offset));
+    }
+
+    fn try_second(&mut self, variable: &V, _pos: &P) {
+        let rescanned = match self.scans.last() {
+            Some((lo, farthest)) => (farthest - lo) as u64,
+            None => 0,
+        };
+        let profile = self.profile_mut(variable);
+        profile.backtracks += 1;
+        profile.rescanned += rescanned;
+    }
+
+    fn terminal_success(&mut self, _terminal_symbol: &TerminalSymbol<T>, _pos: &P, hi: &P) {
+        self.scan(hi);
+    }
+
+    fn terminal_failure(&mut self, _terminal_symbol: &TerminalSymbol<T>, pos: &P) {
+        self.scan(pos);
+    }
+
+    fn exit(&mut self, variable: &V, pos: &P, hi: Option<&P>) {
+        // The caller has scanned as far as `variable`.
+        if let Some((_, farthest)) = self.scans.pop() {
+            if let Some((_, caller_farthest)) = self.scans.last_mut() {
+                *caller_farthest = (*caller_farthest).max(farthest);
+            }
+        }
+
+        let consumed = hi.map(|hi| (self.offset(hi) - self.offset(pos)) as u64);
+        let profile = self.profile_mut(variable);
+        match consumed {
+            Some(consumed) => {
+                profile.successes += 1;
+                profile.consumed += consumed;
+            }
+            None => profile.failures += 1,
+        }
+    }
+
+    fn memo_hit(&mut self, variable: &V, pos: &P, hi: Option<&P>) {
+        self.profile_mut(variable).memo_hits += 1;
+        // The memoized result was scanned as far as where it ends, or at least where it starts.
+        self.scan(hi.unwrap_or(pos));
+    }
+}
+
+/// Shows the report as a table.
+impl<I, V, L> fmt::Display for Profiler<'_, I, V, L>
+where
+    I: Input + ?Sized,
+    V: Clone + Eq + Hash + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<24} {:>12} {:>12} {:>12} {:>12} {:>12} {:>12} {:>12} {:>12}",
+            "variable",
+            "invocations",
+            "memo hits",
+            "successes",
+            "failures",
+            "backtracks",
+            "consumed",
+            "rescanned",
+            "cost"
+        )?;
+        for (variable, profile) in self.report() {
+            writeln!(
+                f,
+                "{:<24} {:>12} {:>12} {:>12} {:>12} {:>12} {:>12} {:>12} {:>12}",
+                format!("{:?}", variable),
+                profile.invocations,
+                profile.memo_hits,
+                profile.successes,
+                profile.failures,
+                profile.backtracks,
+                profile.consumed,
+                profile.rescanned,
+                profile.cost()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::symbols::Metasymbol;
+
+    #[test]
+    fn report_by_cost() {
+        let input = "0123456789";
+        let mut profiler = Profiler::<_, _, u32>::new(input);
+        let observer: &mut dyn ParseObserver<(), char, u32> = &mut profiler;
+
+        // `A` is evaluated 3 times without backtracking.
+        for pos in 0..3 {
+            observer.enter(&'A', &pos);
+            observer.try_first(&'A', &pos);
+            observer.terminal_success(&Metasymbol::Any(1).into(), &pos, &(pos + 1));
+            observer.exit(&'A', &pos, Some(&(pos + 1)));
+        }
+        // `B` is evaluated once, but backtracks after scanning 10 units by the first choice.
+        observer.enter(&'B', &0);
+        observer.try_first(&'B', &0);
+        observer.terminal_success(&Metasymbol::Any(10).into(), &0, &10);
+        observer.terminal_failure(&Metasymbol::Any(1).into(), &10);
+        observer.try_second(&'B', &0);
+        observer.terminal_success(&Metasymbol::Empty.into(), &0, &0);
+        observer.exit(&'B', &0, Some(&0));
+        // The memoized `A` is reused once.
+        observer.memo_hit(&'A', &0, Some(&1));
+
+        let report = profiler.report();
+        assert_eq!(
+            report.iter().map(|(v, _)| *v).collect::<Vec<_>>(),
+            vec!['B', 'A']
+        );
+        assert_eq!(report[0].1.rescanned, 10);
+        assert_eq!(report[0].1.cost(), 11);
+        assert_eq!(report[1].1.memo_hits, 1);
+        assert_eq!(report[1].1.cost(), 4);
+    }
+}
@@ -2,7 +2,7 @@
 
 use crate::choices::{First, Second};
 use crate::symbols::{Equivalence, Metasymbol, TerminalSymbol, E};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
 
 /// This structure is used when defining the right rule for a variable.
@@ -34,6 +34,9 @@ pub enum RightRuleKind<T, V> {
     Failure,
     Any(usize),
     All,
+    Omit,
+    And,
+    Not,
     T(T),
     V(V),
 }
@@ -45,6 +48,9 @@ impl<T, V> From<RightRuleKind<T, V>> for E<T, V> {
             RightRuleKind::Failure => Metasymbol::Failure.into(),
             RightRuleKind::Any(n) => Metasymbol::Any(n).into(),
             RightRuleKind::All => Metasymbol::All.into(),
+            RightRuleKind::Omit => Metasymbol::Omit.into(),
+            RightRuleKind::And => Metasymbol::And.into(),
+            RightRuleKind::Not => Metasymbol::Not.into(),
             RightRuleKind::T(t) => E::T(TerminalSymbol::Original(t)),
             RightRuleKind::V(v) => E::V(v),
         }
@@ -58,6 +64,43 @@ pub type Rule<T, V> = Equivalence<V, RightRule<T, V>>;
 /// `R` is a finite set of rules of the form.
 pub trait Rules<T, V> {
     fn get(&self, variable: &V) -> Option<&RightRule<T, V>>;
+
+    /// Returns variables reachable from `start_variable` which have no right rule.
+    ///
+    /// Parsing with these rules fails with [`ParseErrorKind::MissingRule`](crate::error::ParseErrorKind::MissingRule) if it reaches one of them.
+    fn missing_rules(&self, start_variable: &V) -> Vec<V>
+    where
+        V: Clone + Eq + Hash,
+    {
+        let mut missing_rules = Vec::new();
+        let mut visited = HashSet::new();
+        let mut variables = vec![start_variable.clone()];
+        visited.insert(start_variable.clone());
+
+        while let Some(variable) = variables.pop() {
+            let right_rule = match self.get(&variable) {
+                Some(right_rule) => right_rule,
+                None => {
+                    missing_rules.push(variable);
+                    continue;
+                }
+            };
+
+            for e in [
+                &right_rule.first.lhs,
+                &right_rule.first.rhs,
+                &right_rule.second.0,
+            ] {
+                if let E::V(v) = e {
+                    if visited.insert(v.clone()) {
+                        variables.push(v.clone());
+                    }
+                }
+            }
+        }
+
+        missing_rules
+    }
 }
 
 impl<T, V> Rules<T, V> for HashMap<V, RightRule<T, V>>
@@ -148,4 +191,44 @@ mod tests {
             rules2[&BinDigitVariable::One]
         );
     }
+
+    /// ```
+    /// BinDigit = '0' () / One
+    /// One = '1' () / f
+    /// ```
+    #[test]
+    fn missing_rules() {
+        #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+        enum BinDigitVariable {
+            BinDigit,
+            One,
+        }
+
+        let mut rules: HashMap<BinDigitVariable, RightRule<char, BinDigitVariable>> =
+            HashMap::new();
+
+        rules.insert(
+            BinDigitVariable::BinDigit,
+            RightRule::from_right_rule_kind(
+                (RightRuleKind::T('0'), RightRuleKind::Empty),
+                RightRuleKind::V(BinDigitVariable::One),
+            ),
+        );
+        rules.insert(
+            BinDigitVariable::One,
+            RightRule::from_right_rule_kind(
+                (RightRuleKind::T('1'), RightRuleKind::Empty),
+                RightRuleKind::Failure,
+            ),
+        );
+
+        assert!(Rules::missing_rules(&rules, &BinDigitVariable::BinDigit).is_empty());
+        assert!(Rules::missing_rules(&rules, &BinDigitVariable::One).is_empty());
+
+        rules.remove(&BinDigitVariable::One);
+        assert_eq!(
+            Rules::missing_rules(&rules, &BinDigitVariable::BinDigit),
+            vec![BinDigitVariable::One]
+        );
+    }
 }
@@ -18,6 +18,21 @@ where
     fn lo(&self, input: &I) -> P;
     fn hi(&self, input: &I) -> P;
     fn from_lo_len(lo: P, len: usize, input: &I) -> Self;
+    /// Returns the span of `n` units of input from `lo`. See [`Input::len_of_units`].
+    ///
+    /// Same as `from_lo_len` by default.
+    fn from_lo_units(lo: P, n: usize, input: &I) -> Option<Self> {
+        Some(Self::from_lo_len(lo, n, input))
+    }
+    /// Returns the span of `len` from `lo` if it ends at or before `max_pos`.
+    ///
+    /// Checks `hi` of the span made by `from_lo_len` by default.
+    /// Implementations whose `from_lo_len` may overflow or truncate `len` should check `len` before making the span.
+    fn checked_from_lo_len(lo: P, len: usize, max_pos: &P, input: &I) -> Option<Self> {
+        let span = Self::from_lo_len(lo.clone(), len, input);
+        let hi = span.hi(input);
+        (lo <= hi && &hi <= max_pos).then_some(span)
+    }
     fn from_lo_hi(lo: P, hi: P, input: &I) -> Self;
     /// lhs.hi() and rhs.lo() must be equal.
     fn merge_lhs_and_rhs(lhs: &Self, rhs: &Self, input: &I) -> Self;
@@ -59,6 +59,21 @@ where
         }
     }
 
+    fn from_lo_units(lo: P, n: usize, input: &I) -> Option<Self> {
+        let len = input.len_of_units(P::into_usize(lo.clone(), input), n)?;
+        Some(Self::from_lo_len(lo, len, input))
+    }
+
+    fn checked_from_lo_len(lo: P, len: usize, max_pos: &P, input: &I) -> Option<Self> {
+        let hi = P::into_usize(lo.clone(), input).checked_add(len)?;
+        if hi > P::into_usize(max_pos.clone(), input) {
+            return None;
+        }
+        let span = Self::from_lo_len(lo, len, input);
+        // `len` is truncated if it doesn't fit in `L`.
+        (P::into_usize(span.hi(input), input) == hi).then_some(span)
+    }
+
     fn from_lo_hi(lo: P, hi: P, input: &I) -> Self {
         Self {
             start: lo.clone(),
@@ -144,6 +159,45 @@ mod tests {
         assert_eq!(10, span.hi(input));
     }
 
+    #[test]
+    fn from_lo_units() {
+        let input = "aあ🦀";
+        let span = StartAndLenSpan::<u32, u16>::from_lo_units(1, 2, input).unwrap();
+
+        assert_eq!(1, span.start);
+        assert_eq!(7, span.len);
+        assert_eq!(8, span.hi(input));
+        assert_eq!(
+            StartAndLenSpan::<u32, u16>::from_lo_units(1, 3, input),
+            None
+        );
+
+        let input: &[u8] = "aあ🦀".as_bytes();
+        let span = StartAndLenSpan::<u32, u16>::from_lo_units(1, 2, input).unwrap();
+        assert_eq!(2, span.len);
+    }
+
+    #[test]
+    fn checked_from_lo_len() {
+        let input = "0123456789";
+        let span = StartAndLenSpan::<u32, u32>::checked_from_lo_len(1, 9, &10, input).unwrap();
+
+        assert_eq!(1, span.start);
+        assert_eq!(9, span.len);
+        assert_eq!(
+            StartAndLenSpan::<u32, u32>::checked_from_lo_len(1, 10, &10, input),
+            None
+        );
+        assert_eq!(
+            StartAndLenSpan::<u32, u32>::checked_from_lo_len(1, usize::MAX, &10, input),
+            None
+        );
+        assert_eq!(
+            StartAndLenSpan::<u32, u16>::checked_from_lo_len(0, 0x10000, &0x20000, input),
+            None
+        );
+    }
+
     #[test]
     fn from_lo_hi() {
         let input = "0123456789";
@@ -157,7 +211,6 @@ mod tests {
     #[test]
     fn merge_lhs_and_rhs() {
         let input: String = (0..=54)
-            .into_iter()
             .map(|n: usize| n.to_string())
             .collect::<Vec<String>>()
             .join("");
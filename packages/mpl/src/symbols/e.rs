@@ -64,6 +64,36 @@ macro_rules! e_from {
             $crate::symbols::Metasymbol::All,
         ))
     };
+    (_) => {
+        $crate::symbols::E::T($crate::symbols::TerminalSymbol::Metasymbol(
+            $crate::symbols::Metasymbol::Omit,
+        ))
+    };
+    (Omit) => {
+        $crate::symbols::E::T($crate::symbols::TerminalSymbol::Metasymbol(
+            $crate::symbols::Metasymbol::Omit,
+        ))
+    };
+    (&) => {
+        $crate::symbols::E::T($crate::symbols::TerminalSymbol::Metasymbol(
+            $crate::symbols::Metasymbol::And,
+        ))
+    };
+    (!) => {
+        $crate::symbols::E::T($crate::symbols::TerminalSymbol::Metasymbol(
+            $crate::symbols::Metasymbol::Not,
+        ))
+    };
+    (And) => {
+        $crate::symbols::E::T($crate::symbols::TerminalSymbol::Metasymbol(
+            $crate::symbols::Metasymbol::And,
+        ))
+    };
+    (Not) => {
+        $crate::symbols::E::T($crate::symbols::TerminalSymbol::Metasymbol(
+            $crate::symbols::Metasymbol::Not,
+        ))
+    };
     (Any($len:literal)) => {
         $crate::symbols::E::T($crate::symbols::TerminalSymbol::Metasymbol(
             $crate::symbols::Metasymbol::Any($len),
@@ -105,6 +135,30 @@ mod tests {
             e_from!(?),
             E::<U8SliceTerminal, TestVariables>::T(TerminalSymbol::Metasymbol(Any(1)))
         );
+        assert_eq!(
+            e_from!(_),
+            E::<U8SliceTerminal, TestVariables>::T(TerminalSymbol::Metasymbol(Omit))
+        );
+        assert_eq!(
+            e_from!(Omit),
+            E::<U8SliceTerminal, TestVariables>::T(TerminalSymbol::Metasymbol(Omit))
+        );
+        assert_eq!(
+            e_from!(&),
+            E::<U8SliceTerminal, TestVariables>::T(TerminalSymbol::Metasymbol(And))
+        );
+        assert_eq!(
+            e_from!(!),
+            E::<U8SliceTerminal, TestVariables>::T(TerminalSymbol::Metasymbol(Not))
+        );
+        assert_eq!(
+            e_from!(And),
+            E::<U8SliceTerminal, TestVariables>::T(TerminalSymbol::Metasymbol(And))
+        );
+        assert_eq!(
+            e_from!(Not),
+            E::<U8SliceTerminal, TestVariables>::T(TerminalSymbol::Metasymbol(Not))
+        );
         assert_eq!(
             e_from!(Any(1)),
             E::<U8SliceTerminal, TestVariables>::T(TerminalSymbol::Metasymbol(Any(1)))
@@ -2,8 +2,15 @@
 
 pub use self::e::E;
 pub use self::terminal::{
-    metasymbol::Metasymbol, slice_terminal::SliceTerminal, str_terminal::StrTerminal,
-    terminal_symbol::TerminalSymbol, u8slice_terminal::U8SliceTerminal, Terminal,
+    bits_terminal::BitsTerminal,
+    byte_set::ByteSet,
+    metasymbol::Metasymbol,
+    predicate::Predicate,
+    slice_terminal::{ElementRange, SliceTerminal},
+    str_terminal::StrTerminal,
+    terminal_symbol::TerminalSymbol,
+    u8slice_terminal::U8SliceTerminal,
+    varint, Terminal,
 };
 pub use self::variable::Variable;
 
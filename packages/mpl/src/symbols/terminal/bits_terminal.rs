@@ -0,0 +1,85 @@
+use crate::input::{BitOrder, BitSlice};
+use crate::span::{Len, Span, Start, StartAndLenSpan};
+use crate::symbols::terminal::StartAndLenResult;
+use crate::symbols::{Metasymbol, Terminal};
+use crate::trees::AST;
+use std::fmt;
+
+/// Terminal symbols of N-bit fields in [`BitSlice`].
+///
+/// The first of each variant is the number of bits, which is at most 64.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum BitsTerminal {
+    /// A field of the value whose most significant bit comes first.
+    Msb(u8, u64),
+    /// A field of the value whose least significant bit comes first.
+    Lsb(u8, u64),
+    /// Any field.
+    Field(u8),
+}
+
+impl fmt::Display for BitsTerminal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Msb(n, value) => write!(f, "{:#b} in {} bits MSB first", value, n),
+            Self::Lsb(n, value) => write!(f, "{:#b} in {} bits LSB first", value, n),
+            Self::Field(n) => write!(f, "{} bits", n),
+        }
+    }
+}
+
+impl<'i, 'a, V, P, L, O> Terminal<'i, BitSlice<'a>, V, StartAndLenSpan<P, L>, P, O> for BitsTerminal
+where
+    P: Start<BitSlice<'a>, L>,
+    L: Len<BitSlice<'a>, P>,
+{
+    fn eval(&self, input: &'i BitSlice<'a>, pos: P, max_pos: &P) -> StartAndLenResult<V, P, L, O> {
+        let (n, value, order) = match self {
+            Self::Msb(n, value) => (*n, Some(*value), BitOrder::MsbFirst),
+            Self::Lsb(n, value) => (*n, Some(*value), BitOrder::LsbFirst),
+            Self::Field(n) => (*n, None, BitOrder::MsbFirst),
+        };
+
+        let lo = P::into_usize(pos.clone(), input);
+        let span = StartAndLenSpan::from_lo_len(pos, n.into(), input);
+        let hi = span.hi(input);
+        let ast = AST::from_leaf(Metasymbol::Omit.into(), span);
+        if &hi <= max_pos {
+            match (input.read(lo, n.into(), order), value) {
+                (Some(field), Some(value)) if field == value => return Ok(ast),
+                (Some(_), None) => return Ok(ast),
+                _ => {}
+            }
+        }
+        Err(ast)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn field() {
+        let input = BitSlice::new(&[0b1110_0101], BitOrder::MsbFirst);
+        let eval = |terminal: BitsTerminal, pos: u32, max_pos: u32| {
+            let result: StartAndLenResult<(), u32, u32, ()> = terminal.eval(&input, pos, &max_pos);
+            result.map(|ast| ast.span.len).map_err(|ast| ast.span.len)
+        };
+
+        assert_eq!(eval(BitsTerminal::Msb(3, 0b111), 0, 8), Ok(3));
+        assert_eq!(eval(BitsTerminal::Msb(3, 0b100), 2, 8), Ok(3));
+        assert_eq!(eval(BitsTerminal::Lsb(3, 0b001), 2, 8), Ok(3));
+        assert_eq!(eval(BitsTerminal::Msb(3, 0b001), 2, 8), Err(3));
+        assert_eq!(eval(BitsTerminal::Field(5), 3, 8), Ok(5));
+        // Out of `max_pos`
+        assert_eq!(eval(BitsTerminal::Field(5), 3, 7), Err(5));
+        assert_eq!(eval(BitsTerminal::Field(2), 7, 8), Err(2));
+
+        assert_eq!(
+            BitsTerminal::Msb(3, 0b101).to_string(),
+            "0b101 in 3 bits MSB first"
+        );
+        assert_eq!(BitsTerminal::Field(11).to_string(), "11 bits");
+    }
+}
@@ -0,0 +1,88 @@
+use std::fmt;
+
+/// Bitmap of bytes.
+///
+/// This can be built in a constant.
+///
+/// ```
+/// use mpl::symbols::ByteSet;
+///
+/// const HEX_DIGITS: ByteSet = ByteSet::new()
+///     .with_range(b'0', b'9')
+///     .with_range(b'A', b'F')
+///     .with_range(b'a', b'f');
+///
+/// assert!(HEX_DIGITS.contains(b'c'));
+/// assert!(!HEX_DIGITS.contains(b'g'));
+/// ```
+#[derive(Copy, Clone, Default, Eq, Hash, PartialEq)]
+pub struct ByteSet([u64; 4]);
+
+impl ByteSet {
+    /// Returns the empty set.
+    pub const fn new() -> Self {
+        Self([0; 4])
+    }
+
+    /// Returns the set of `bytes`.
+    pub const fn from_bytes(bytes: &[u8]) -> Self {
+        let mut set = Self::new();
+        let mut i = 0;
+        while i < bytes.len() {
+            set = set.with(bytes[i]);
+            i += 1;
+        }
+        set
+    }
+
+    /// Returns the set with `byte` added.
+    pub const fn with(mut self, byte: u8) -> Self {
+        self.0[(byte / 64) as usize] |= 1 << (byte % 64);
+        self
+    }
+
+    /// Returns the set with the inclusive range from `start` to `end` added.
+    pub const fn with_range(mut self, start: u8, end: u8) -> Self {
+        let mut byte = start;
+        while byte <= end {
+            self = self.with(byte);
+            if byte == u8::MAX {
+                break;
+            }
+            byte += 1;
+        }
+        self
+    }
+
+    pub const fn contains(&self, byte: u8) -> bool {
+        self.0[(byte / 64) as usize] & (1 << (byte % 64)) != 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = u8> + '_ {
+        (0..=u8::MAX).filter(move |&byte| self.contains(byte))
+    }
+}
+
+impl fmt::Debug for ByteSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn byte_set() {
+        let set = ByteSet::from_bytes(b"+-").with_range(b'0', b'2');
+
+        assert_eq!(set.iter().collect::<Vec<_>>(), b"+-012");
+        assert!(!set.contains(b'3'));
+        assert_eq!(format!("{:?}", set), "{43, 45, 48, 49, 50}");
+
+        let all = ByteSet::new().with_range(0, u8::MAX);
+        assert_eq!(all.iter().count(), 256);
+        assert!(ByteSet::new().with_range(1, 0).iter().next().is_none());
+    }
+}
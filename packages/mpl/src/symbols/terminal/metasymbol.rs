@@ -1,3 +1,5 @@
+use std::fmt;
+
 /// Metasymbol.
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum Metasymbol {
@@ -19,6 +21,36 @@ pub enum Metasymbol {
     ///
     /// Same as `All = ? All / ()`.
     All,
-    /// `Omit` means that some information has been omitted.
+    /// `_` is a metasymbol that always succeeds without consuming input, and omits the subtree of the first choice.
+    ///
+    /// `Omitted = _ A / f` matches the same input as `A`, but the AST of `Omitted` has only an `Omit` leaf instead of the subtree of `A`.
+    ///
+    /// `Omit` also means that some information has been omitted.
     Omit,
+    /// `&` is a metasymbol of the and-predicate. This succeeds without consuming input if the right-hand side of the first choice succeeds.
+    ///
+    /// `And = & A / f` succeeds if `A` succeeds, but doesn't consume the input matched by `A`.
+    ///
+    /// Alone, `&` always succeeds without consuming input like `()`.
+    And,
+    /// `!` is a metasymbol of the not-predicate. This succeeds without consuming input if the right-hand side of the first choice fails.
+    ///
+    /// `Not = ! A / f` succeeds if `A` fails.
+    ///
+    /// Alone, `!` always fails without consuming input like `f`.
+    Not,
+}
+
+impl fmt::Display for Metasymbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "()"),
+            Self::Failure => write!(f, "f"),
+            Self::Any(n) => write!(f, "{}", "?".repeat(*n)),
+            Self::All => write!(f, "*"),
+            Self::Omit => write!(f, "_"),
+            Self::And => write!(f, "&"),
+            Self::Not => write!(f, "!"),
+        }
+    }
 }
@@ -1,11 +1,18 @@
-use crate::span::StartAndLenSpan;
+use crate::input::Input;
+use crate::span::{Len, Span, Start, StartAndLenSpan};
+use crate::symbols::Metasymbol;
 use crate::trees::AST;
+use std::ops::Range;
 
+pub mod bits_terminal;
+pub mod byte_set;
 pub mod metasymbol;
+pub mod predicate;
 pub mod slice_terminal;
 pub mod str_terminal;
 pub mod terminal_symbol;
 pub mod u8slice_terminal;
+pub mod varint;
 
 /// Original terminal symbol types.
 pub trait Terminal<'i, I, V, S, P, O>
@@ -17,3 +24,76 @@ where
 
 type StartAndLenResult<V, P, L, O> =
     Result<AST<V, StartAndLenSpan<P, L>, O>, AST<V, StartAndLenSpan<P, L>, O>>;
+
+/// Evaluates a terminal symbol matching a single char if `c`, the char at `pos`, satisfies `f`.
+fn eval_char<I, V, P, L, O, F>(
+    input: &I,
+    pos: P,
+    max_pos: &P,
+    c: Option<char>,
+    f: F,
+) -> StartAndLenResult<V, P, L, O>
+where
+    I: Input + ?Sized,
+    P: Start<I, L>,
+    L: Len<I, P>,
+    F: FnOnce(char) -> bool,
+{
+    let span = StartAndLenSpan::from_lo_len(pos, c.map_or(0, char::len_utf8), input);
+    let hi = span.hi(input);
+    let ast = AST::from_leaf(Metasymbol::Omit.into(), span);
+    match c {
+        Some(c) if &hi <= max_pos && f(c) => Ok(ast),
+        _ => Err(ast),
+    }
+}
+
+/// Returns the length of the prefix of `input` equal to `s` under Unicode simple case folding.
+fn len_of_case_folded(input: &str, s: &str) -> Option<usize> {
+    let fold = |c: char| {
+        unicode_case_mapping::case_folded(c)
+            .and_then(|folded| char::from_u32(folded.get()))
+            .unwrap_or(c)
+    };
+
+    let mut chars = input.char_indices();
+    for c in s.chars() {
+        let (_, input_c) = chars.next()?;
+        if fold(input_c) != fold(c) {
+            return None;
+        }
+    }
+    Some(chars.next().map_or(input.len(), |(i, _)| i))
+}
+
+/// Evaluates a terminal symbol matching the length which `len_of` returns for the rest of input from `pos` to `max_pos`.
+///
+/// `get` returns the subrange of input.
+fn eval_len_of<I, V, P, L, O, F>(
+    input: &I,
+    pos: P,
+    max_pos: &P,
+    len_of: F,
+    get: fn(&I, Range<usize>) -> Option<&I>,
+) -> StartAndLenResult<V, P, L, O>
+where
+    I: Input + ?Sized,
+    P: Start<I, L>,
+    L: Len<I, P>,
+    F: FnOnce(&I) -> Option<usize>,
+{
+    let lo = P::into_usize(pos.clone(), input);
+    let hi = P::into_usize(max_pos.clone(), input);
+    let len = get(input, lo..hi).and_then(|rest| {
+        let len = len_of(rest)?;
+        get(rest, 0..len).map(|_| len)
+    });
+    let ast = AST::from_leaf(
+        Metasymbol::Omit.into(),
+        StartAndLenSpan::from_lo_len(pos, len.unwrap_or(0), input),
+    );
+    match len {
+        Some(_) => Ok(ast),
+        None => Err(ast),
+    }
+}
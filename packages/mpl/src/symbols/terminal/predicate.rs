@@ -0,0 +1,113 @@
+use std::fmt;
+use std::ptr;
+
+type LenOf<'a, I> = dyn Fn(&I) -> Option<usize> + Send + Sync + 'a;
+
+/// Predicate over the rest of input.
+///
+/// A predicate returns the length of input it matches from the start of the rest of input, or `None` if it doesn't match.
+/// The rest of input ends at `max_pos`, and a length that is out of it or not at a `char` boundary doesn't match.
+///
+/// A predicate of a fn pointer can be built in a constant.
+///
+/// ```
+/// use mpl::symbols::Predicate;
+///
+/// static ASCII: Predicate<[u8]> = Predicate::new(|rest| match rest.first() {
+///     Some(b) if b & 0x80 == 0 => Some(1),
+///     _ => None,
+/// });
+///
+/// assert_eq!(ASCII.len_of(b"a"), Some(1));
+/// assert_eq!(ASCII.len_of(&[0xff]), None);
+///
+/// let digits = Predicate::<str>::boxed(|rest| {
+///     let len = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+///     Some(len).filter(|&len| len != 0)
+/// });
+///
+/// assert_eq!(digits.len_of("123a"), Some(3));
+/// ```
+pub struct Predicate<'a, I: ?Sized>(Kind<'a, I>);
+
+enum Kind<'a, I: ?Sized> {
+    Fn(fn(&I) -> Option<usize>),
+    Boxed(Box<LenOf<'a, I>>),
+}
+
+impl<'a, I: ?Sized> Predicate<'a, I> {
+    /// Returns the predicate of the fn pointer.
+    pub const fn new(f: fn(&I) -> Option<usize>) -> Self {
+        Self(Kind::Fn(f))
+    }
+
+    /// Returns the predicate of the closure.
+    pub fn boxed<F>(f: F) -> Self
+    where
+        F: Fn(&I) -> Option<usize> + Send + Sync + 'a,
+    {
+        Self(Kind::Boxed(Box::new(f)))
+    }
+
+    /// Returns the length of input matched from the start of `rest`.
+    pub fn len_of(&self, rest: &I) -> Option<usize> {
+        match &self.0 {
+            Kind::Fn(f) => f(rest),
+            Kind::Boxed(f) => f(rest),
+        }
+    }
+}
+
+impl<'a> Predicate<'a, str> {
+    /// Returns the predicate matching a single char which satisfies `f`.
+    pub fn char<F>(f: F) -> Self
+    where
+        F: Fn(char) -> bool + Send + Sync + 'a,
+    {
+        Self::boxed(move |rest: &str| rest.chars().next().filter(|&c| f(c)).map(char::len_utf8))
+    }
+}
+
+impl<'a, T> Predicate<'a, [T]> {
+    /// Returns the predicate matching a single element which satisfies `f`.
+    pub fn element<F>(f: F) -> Self
+    where
+        F: Fn(&T) -> bool + Send + Sync + 'a,
+    {
+        Self::boxed(move |rest: &[T]| rest.first().filter(|e| f(e)).map(|_| 1))
+    }
+}
+
+impl<I: ?Sized> fmt::Debug for Predicate<'_, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Predicate").finish_non_exhaustive()
+    }
+}
+
+/// Predicates are equal only if they are the same predicate.
+impl<I: ?Sized> PartialEq for Predicate<'_, I> {
+    fn eq(&self, other: &Self) -> bool {
+        ptr::eq(self, other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn predicate() {
+        let vowel = Predicate::<str>::char(|c| "aeiou".contains(c));
+        assert_eq!(vowel.len_of("ab"), Some(1));
+        assert_eq!(vowel.len_of("b"), None);
+        assert_eq!(vowel.len_of(""), None);
+
+        let even = Predicate::<[u32]>::element(|e| e % 2 == 0);
+        assert_eq!(even.len_of(&[2, 3]), Some(1));
+        assert_eq!(even.len_of(&[3]), None);
+
+        let other = Predicate::<str>::char(|c| "aeiou".contains(c));
+        assert_eq!(vowel, vowel);
+        assert_ne!(vowel, other);
+    }
+}
@@ -1,13 +1,64 @@
 use crate::span::{Len, Span, Start, StartAndLenSpan};
-use crate::symbols::terminal::StartAndLenResult;
-use crate::symbols::{Metasymbol, Terminal};
+use crate::symbols::terminal::{eval_len_of, StartAndLenResult};
+use crate::symbols::{Metasymbol, Predicate, Terminal};
 use crate::trees::AST;
 use std::cmp::PartialEq;
+use std::fmt;
 
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum SliceTerminal<'a, T> {
     Element(T),
     Slice(&'a [T]),
+    /// An element in the inclusive range.
+    Range(ElementRange<T>),
+    /// Elements matched by the predicate.
+    Predicate(&'a Predicate<'a, [T]>),
+}
+
+impl<T: PartialOrd> SliceTerminal<'_, T> {
+    /// Returns the terminal matching an element in `start..=end`.
+    pub const fn range(start: T, end: T) -> Self {
+        Self::Range(ElementRange::new(start, end))
+    }
+}
+
+/// Inclusive range of elements of [`SliceTerminal::Range`].
+///
+/// The range keeps how to compare elements, so other variants of `SliceTerminal` only need `T: PartialEq`.
+#[derive(Copy, Clone)]
+pub struct ElementRange<T> {
+    pub start: T,
+    pub end: T,
+    contains: fn(&T, &T, &T) -> bool,
+}
+
+impl<T: PartialOrd> ElementRange<T> {
+    pub const fn new(start: T, end: T) -> Self {
+        Self {
+            start,
+            end,
+            contains: |start, end, element| start <= element && element <= end,
+        }
+    }
+}
+
+impl<T> ElementRange<T> {
+    /// Returns true if `element` is in the range.
+    pub fn contains(&self, element: &T) -> bool {
+        (self.contains)(&self.start, &self.end, element)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ElementRange<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}..={:?}", self.start, self.end)
+    }
+}
+
+impl<T: PartialEq> PartialEq for ElementRange<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.start == other.start && self.end == other.end
+    }
 }
 
 impl<T> From<T> for SliceTerminal<'_, T> {
@@ -22,6 +73,17 @@ impl<'a, T> From<&'a [T]> for SliceTerminal<'a, T> {
     }
 }
 
+impl<T: fmt::Debug> fmt::Display for SliceTerminal<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Element(element) => write!(f, "{:?}", element),
+            Self::Slice(slice) => write!(f, "{:?}", slice),
+            Self::Range(range) => write!(f, "{:?}", range),
+            Self::Predicate(_) => write!(f, "predicate"),
+        }
+    }
+}
+
 impl<'a, T: PartialEq, V, P, L, O> Terminal<'a, [T], V, StartAndLenSpan<P, L>, P, O>
     for SliceTerminal<'a, T>
 where
@@ -52,6 +114,18 @@ where
                 }
                 Err(ast)
             }
+            SliceTerminal::Range(range) => {
+                // Length is 1.
+                let (ast, hi, pos) = ast_hi_pos(pos, 1);
+                if &hi <= max_pos {
+                    if let Some(e) = input.get(pos) {
+                        if range.contains(e) {
+                            return Ok(ast);
+                        }
+                    }
+                }
+                Err(ast)
+            }
             SliceTerminal::Slice(slice) => {
                 let len = slice.len();
                 let (ast, hi, pos) = ast_hi_pos(pos, len);
@@ -64,6 +138,13 @@ where
                 }
                 Err(ast)
             }
+            SliceTerminal::Predicate(predicate) => eval_len_of(
+                input,
+                pos,
+                max_pos,
+                |rest| predicate.len_of(rest),
+                |input, range| input.get(range),
+            ),
         }
     }
 }
@@ -80,4 +161,40 @@ mod tests {
         assert_eq!(c, SliceTerminal::Element('A'));
         assert_eq!(s, SliceTerminal::Slice(&['a', 'b', 'c']));
     }
+
+    #[test]
+    fn range() {
+        let input = [1.5, 3.0];
+        let eval = |terminal: SliceTerminal<f64>, pos: u32| {
+            let result: StartAndLenResult<(), u32, u32, ()> = terminal.eval(&input, pos, &2);
+            result.is_ok()
+        };
+
+        assert!(eval(SliceTerminal::range(1.0, 2.0), 0));
+        assert!(!eval(SliceTerminal::range(1.0, 2.0), 1));
+        assert!(!eval(SliceTerminal::range(1.0, 2.0), 2));
+        assert_eq!(SliceTerminal::range(1.0, 2.0).to_string(), "1.0..=2.0");
+    }
+
+    #[test]
+    fn predicate() {
+        let input = [2, 4, 5];
+        let even = Predicate::element(|e: &i32| e % 2 == 0);
+        let evens =
+            Predicate::boxed(|rest: &[i32]| Some(rest.iter().take_while(|e| *e % 2 == 0).count()));
+        // Out of `max_pos`
+        let too_long = Predicate::new(|_: &[i32]| Some(4));
+        let eval = |terminal: SliceTerminal<i32>, pos: u32| {
+            let result: StartAndLenResult<(), u32, u32, ()> = terminal.eval(&input, pos, &3);
+            result.map(|ast| ast.span.len).map_err(|ast| ast.span.len)
+        };
+
+        assert_eq!(eval(SliceTerminal::Predicate(&even), 0), Ok(1));
+        assert_eq!(eval(SliceTerminal::Predicate(&even), 2), Err(0));
+        assert_eq!(eval(SliceTerminal::Predicate(&even), 3), Err(0));
+        assert_eq!(eval(SliceTerminal::Predicate(&evens), 0), Ok(2));
+        assert_eq!(eval(SliceTerminal::Predicate(&evens), 2), Ok(0));
+        assert_eq!(eval(SliceTerminal::Predicate(&too_long), 0), Err(0));
+        assert_eq!(SliceTerminal::Predicate(&even).to_string(), "predicate");
+    }
 }
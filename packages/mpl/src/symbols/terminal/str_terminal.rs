@@ -1,12 +1,58 @@
+#[cfg(feature = "grapheme")]
+use crate::input::Graphemes;
 use crate::span::{Len, Span, Start, StartAndLenSpan};
-use crate::symbols::terminal::StartAndLenResult;
-use crate::symbols::{Metasymbol, Terminal};
+use crate::symbols::terminal::{eval_char, eval_len_of, len_of_case_folded, StartAndLenResult};
+use crate::symbols::{Metasymbol, Predicate, Terminal};
 use crate::trees::AST;
+use std::fmt;
+use unicode_xid::UnicodeXID;
 
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum StrTerminal<'a> {
     Char(char),
     Str(&'a str),
+    /// A string ignoring ASCII case.
+    IStr(&'a str),
+    /// A string ignoring case by Unicode simple case folding.
+    FoldStr(&'a str),
+    /// A char in the inclusive range like `'a'..='z'`.
+    CharRange(char, char),
+    /// A char in the string.
+    CharSet(&'a str),
+    /// A char with the Unicode `Alphabetic` property.
+    Alphabetic,
+    /// A char with the Unicode `Numeric` property.
+    Numeric,
+    /// A char with the Unicode `White_Space` property.
+    Whitespace,
+    /// A char with the Unicode `XID_Start` property.
+    XidStart,
+    /// A char with the Unicode `XID_Continue` property.
+    XidContinue,
+    /// Input matched by the predicate.
+    Predicate(&'a Predicate<'a, str>),
+}
+
+impl StrTerminal<'_> {
+    /// Returns true if `c` is in the char class.
+    ///
+    /// Strings and predicates are not char classes.
+    fn contains_char(&self, c: char) -> bool {
+        match self {
+            Self::Char(_)
+            | Self::Str(_)
+            | Self::IStr(_)
+            | Self::FoldStr(_)
+            | Self::Predicate(_) => false,
+            Self::CharRange(start, end) => (*start..=*end).contains(&c),
+            Self::CharSet(s) => s.contains(c),
+            Self::Alphabetic => c.is_alphabetic(),
+            Self::Numeric => c.is_numeric(),
+            Self::Whitespace => c.is_whitespace(),
+            Self::XidStart => c.is_xid_start(),
+            Self::XidContinue => c.is_xid_continue(),
+        }
+    }
 }
 
 impl From<char> for StrTerminal<'_> {
@@ -21,35 +67,95 @@ impl<'a> From<&'a str> for StrTerminal<'a> {
     }
 }
 
+impl fmt::Display for StrTerminal<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Char(c) => write!(f, "{:?}", c),
+            Self::Str(s) => write!(f, "{:?}", s),
+            Self::IStr(s) => write!(f, "{:?} ignoring ASCII case", s),
+            Self::FoldStr(s) => write!(f, "{:?} ignoring case", s),
+            Self::CharRange(start, end) => write!(f, "{:?}..={:?}", start, end),
+            Self::CharSet(s) => write!(f, "one of {:?}", s),
+            Self::Alphabetic => write!(f, "alphabetic"),
+            Self::Numeric => write!(f, "numeric"),
+            Self::Whitespace => write!(f, "whitespace"),
+            Self::XidStart => write!(f, "XID_Start"),
+            Self::XidContinue => write!(f, "XID_Continue"),
+            Self::Predicate(_) => write!(f, "predicate"),
+        }
+    }
+}
+
 impl<'a, V, P, L, O> Terminal<'a, str, V, StartAndLenSpan<P, L>, P, O> for StrTerminal<'a>
 where
     P: Start<str, L>,
     L: Len<str, P>,
 {
     fn eval(&self, input: &'a str, pos: P, max_pos: &P) -> StartAndLenResult<V, P, L, O> {
-        let eval_from = |len: usize, string: &str| {
+        let eval_len = |len: usize, f: &dyn Fn(&str) -> bool| {
             let start = pos.clone();
-            let pos: usize = P::into_usize(pos, input);
+            let pos: usize = P::into_usize(pos.clone(), input);
             let span = StartAndLenSpan::from_lo_len(start, len, input);
             let hi = span.hi(input);
             let ast = AST::from_leaf(Metasymbol::Omit.into(), span);
             if &hi <= max_pos {
                 if let Some(s) = input.get(pos..pos + len) {
-                    if s == string {
+                    if f(s) {
                         return Ok(ast);
                     }
                 }
             }
             Err(ast)
         };
+        let eval_from = |len: usize, string: &str| eval_len(len, &|s| s == string);
 
         match self {
             StrTerminal::Char(c) => eval_from(c.len_utf8(), &c.to_string()),
             StrTerminal::Str(s) => eval_from(s.len(), s),
+            StrTerminal::IStr(s) => eval_len(s.len(), &|t| t.eq_ignore_ascii_case(s)),
+            StrTerminal::FoldStr(s) => {
+                let rest = input.get(P::into_usize(pos.clone(), input)..).unwrap_or("");
+                match len_of_case_folded(rest, s) {
+                    Some(len) => eval_len(len, &|_| true),
+                    None => eval_len(s.len(), &|_| false),
+                }
+            }
+            StrTerminal::Predicate(predicate) => eval_len_of(
+                input,
+                pos,
+                max_pos,
+                |rest| predicate.len_of(rest),
+                |input, range| input.get(range),
+            ),
+            StrTerminal::CharRange(..)
+            | StrTerminal::CharSet(_)
+            | StrTerminal::Alphabetic
+            | StrTerminal::Numeric
+            | StrTerminal::Whitespace
+            | StrTerminal::XidStart
+            | StrTerminal::XidContinue => {
+                let c = input
+                    .get(P::into_usize(pos.clone(), input)..)
+                    .and_then(|s| s.chars().next());
+                eval_char(input, pos, max_pos, c, |c| self.contains_char(c))
+            }
         }
     }
 }
 
+/// Evaluates as in `str`, since [`Graphemes`] only changes the unit of `?`.
+#[cfg(feature = "grapheme")]
+impl<'i, 'a, V, P, L, O> Terminal<'i, Graphemes<'a>, V, StartAndLenSpan<P, L>, P, O>
+    for StrTerminal<'a>
+where
+    P: Start<str, L>,
+    L: Len<str, P>,
+{
+    fn eval(&self, input: &'i Graphemes<'a>, pos: P, max_pos: &P) -> StartAndLenResult<V, P, L, O> {
+        self.eval(input.as_str(), pos, max_pos)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -62,4 +168,72 @@ mod tests {
         assert_eq!(c, StrTerminal::Char('A'));
         assert_eq!(s, StrTerminal::Str("abc"));
     }
+
+    #[test]
+    fn char_class() {
+        let input = "aZ9 あ_";
+        let max_pos = input.len() as u32;
+        let eval = |terminal: StrTerminal, pos: u32| {
+            let result: StartAndLenResult<(), u32, u32, ()> = terminal.eval(input, pos, &max_pos);
+            result.map(|ast| ast.span.len).map_err(|ast| ast.span.len)
+        };
+
+        assert_eq!(eval(StrTerminal::CharRange('a', 'z'), 0), Ok(1));
+        assert_eq!(eval(StrTerminal::CharRange('a', 'z'), 1), Err(1));
+        assert_eq!(eval(StrTerminal::CharSet("+-9"), 2), Ok(1));
+        assert_eq!(eval(StrTerminal::Alphabetic, 4), Ok(3));
+        assert_eq!(eval(StrTerminal::Alphabetic, 2), Err(1));
+        assert_eq!(eval(StrTerminal::Numeric, 2), Ok(1));
+        assert_eq!(eval(StrTerminal::Whitespace, 3), Ok(1));
+        assert_eq!(eval(StrTerminal::XidStart, 7), Err(1));
+        assert_eq!(eval(StrTerminal::XidContinue, 7), Ok(1));
+        // No input left
+        assert_eq!(eval(StrTerminal::XidContinue, 8), Err(0));
+        // Not at a char boundary
+        assert_eq!(eval(StrTerminal::Alphabetic, 5), Err(0));
+    }
+
+    #[test]
+    fn ignore_case() {
+        let input = "SELECT \u{212A}ELVIN σας";
+        let max_pos = input.len() as u32;
+        let eval = |terminal: StrTerminal, pos: u32| {
+            let result: StartAndLenResult<(), u32, u32, ()> = terminal.eval(input, pos, &max_pos);
+            result.map(|ast| ast.span.len).map_err(|ast| ast.span.len)
+        };
+
+        assert_eq!(eval(StrTerminal::IStr("select"), 0), Ok(6));
+        assert_eq!(eval(StrTerminal::IStr("selects"), 0), Err(7));
+        assert_eq!(eval(StrTerminal::FoldStr("select"), 0), Ok(6));
+        // KELVIN SIGN is folded to 'k' only by Unicode case folding.
+        assert_eq!(eval(StrTerminal::IStr("kelvin"), 7), Err(6));
+        assert_eq!(eval(StrTerminal::FoldStr("kelvin"), 7), Ok(8));
+        assert_eq!(eval(StrTerminal::FoldStr("ΣΑΣ"), 16), Ok(6));
+        assert_eq!(eval(StrTerminal::FoldStr("σας!"), 16), Err(7));
+
+        assert_eq!(
+            StrTerminal::IStr("select").to_string(),
+            "\"select\" ignoring ASCII case"
+        );
+        assert_eq!(
+            StrTerminal::FoldStr("select").to_string(),
+            "\"select\" ignoring case"
+        );
+    }
+
+    #[test]
+    fn predicate() {
+        let input = "あい";
+        let kana = Predicate::char(|c| ('\u{3040}'..='\u{309f}').contains(&c));
+        // Not at a char boundary
+        let half = Predicate::new(|_: &str| Some(1));
+        let eval = |terminal: StrTerminal, pos: u32| {
+            let result: StartAndLenResult<(), u32, u32, ()> = terminal.eval(input, pos, &6);
+            result.map(|ast| ast.span.len).map_err(|ast| ast.span.len)
+        };
+
+        assert_eq!(eval(StrTerminal::Predicate(&kana), 3), Ok(3));
+        assert_eq!(eval(StrTerminal::Predicate(&kana), 6), Err(0));
+        assert_eq!(eval(StrTerminal::Predicate(&half), 0), Err(0));
+    }
 }
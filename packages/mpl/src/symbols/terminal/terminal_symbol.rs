@@ -1,4 +1,5 @@
 use crate::symbols::Metasymbol;
+use std::fmt;
 
 /// Terminal symbol.
 #[derive(Clone, Debug, PartialEq)]
@@ -19,6 +20,15 @@ impl<T> TerminalSymbol<T> {
     }
 }
 
+impl<T: fmt::Display> fmt::Display for TerminalSymbol<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Original(original) => original.fmt(f),
+            Self::Metasymbol(metasymbol) => metasymbol.fmt(f),
+        }
+    }
+}
+
 impl<T> TerminalSymbol<T> {
     pub fn as_original(&self) -> Option<&T> {
         match self {
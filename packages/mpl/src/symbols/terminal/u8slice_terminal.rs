@@ -1,45 +1,239 @@
 use crate::span::{Len, Span, Start, StartAndLenSpan};
-use crate::symbols::terminal::StartAndLenResult;
-use crate::symbols::{Metasymbol, Terminal};
+use crate::symbols::terminal::{eval_char, eval_len_of, len_of_case_folded, StartAndLenResult};
+use crate::symbols::{varint, ByteSet, Metasymbol, Predicate, Terminal};
 use crate::trees::AST;
 use std::cmp::PartialEq;
+use std::fmt;
 use std::mem;
+use std::str;
+use unicode_xid::UnicodeXID;
 
-#[derive(Copy, Clone, Debug, PartialEq)]
-pub enum U8SliceTerminal<'a> {
-    Char(char),
-    Str(&'a str),
-    U8Slice(&'a [u8]),
-    // Big Endian
-    BEf32(f32),
-    BEf64(f64),
-    BEu8(u8),
-    BEi8(i8),
-    BEu16(u16),
-    BEi16(i16),
-    BEu32(u32),
-    BEi32(i32),
-    BEu64(u64),
-    BEi64(i64),
-    BEu128(u128),
-    BEi128(i128),
-    BEusize(usize),
-    BEisize(isize),
-    // Little Endian
-    LEf32(f32),
-    LEf64(f64),
-    LEu8(u8),
-    LEi8(i8),
-    LEu16(u16),
-    LEi16(i16),
-    LEu32(u32),
-    LEi32(i32),
-    LEu64(u64),
-    LEi64(i64),
-    LEu128(u128),
-    LEi128(i128),
-    LEusize(usize),
-    LEisize(isize),
+/// Defines the enum with variants of each integer type checked by a range of values or by a mask and a value.
+///
+/// Also defines the `checked_int!()` pattern, which matches those variants.
+macro_rules! checked_int_terminal {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident<$lt:lifetime> {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident $(($($field:ty),*))?,
+            )*
+        }
+        $($int:ident: $be_range:ident, $le_range:ident, $be_mask:ident, $le_mask:ident;)*
+    ) => {
+        $(#[$meta])*
+        pub enum $name<$lt> {
+            $(
+                $(#[$variant_meta])*
+                $variant $(($($field),*))?,
+            )*
+            $(
+                #[doc = concat!("A big endian `", stringify!($int), "` in the inclusive range.")]
+                $be_range($int, $int),
+                #[doc = concat!("A little endian `", stringify!($int), "` in the inclusive range.")]
+                $le_range($int, $int),
+                #[doc = concat!("A big endian `", stringify!($int), "` which matches `n & mask == value` with a mask and a value.")]
+                $be_mask($int, $int),
+                #[doc = concat!("A little endian `", stringify!($int), "` which matches `n & mask == value` with a mask and a value.")]
+                $le_mask($int, $int),
+            )*
+        }
+
+        macro_rules! checked_int {
+            () => {
+                $($name::$be_range(..) | $name::$le_range(..) | $name::$be_mask(..) | $name::$le_mask(..))|*
+            };
+        }
+
+        impl $name<'_> {
+            /// Returns the length of the integer if `self` checks an integer by a range or a mask.
+            fn checked_int_len(&self) -> Option<usize> {
+                match self {
+                    $(
+                        Self::$be_range(..)
+                        | Self::$le_range(..)
+                        | Self::$be_mask(..)
+                        | Self::$le_mask(..) => Some(mem::size_of::<$int>()),
+                    )*
+                    $(Self::$variant { .. })|* => None,
+                }
+            }
+
+            /// Returns true if `bytes` is an integer in the range or matched by the mask.
+            ///
+            /// `bytes` of a wrong length don't match.
+            fn checked_int_matches(&self, bytes: &[u8]) -> bool {
+                match self {
+                    $(
+                        Self::$be_range(start, end) => <[u8; mem::size_of::<$int>()]>::try_from(bytes)
+                            .is_ok_and(|bytes| (*start..=*end).contains(&$int::from_be_bytes(bytes))),
+                        Self::$le_range(start, end) => <[u8; mem::size_of::<$int>()]>::try_from(bytes)
+                            .is_ok_and(|bytes| (*start..=*end).contains(&$int::from_le_bytes(bytes))),
+                        Self::$be_mask(mask, value) => <[u8; mem::size_of::<$int>()]>::try_from(bytes)
+                            .is_ok_and(|bytes| $int::from_be_bytes(bytes) & mask == *value),
+                        Self::$le_mask(mask, value) => <[u8; mem::size_of::<$int>()]>::try_from(bytes)
+                            .is_ok_and(|bytes| $int::from_le_bytes(bytes) & mask == *value),
+                    )*
+                    $(Self::$variant { .. })|* => false,
+                }
+            }
+        }
+    };
+}
+
+checked_int_terminal! {
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    pub enum U8SliceTerminal<'a> {
+        Char(char),
+        Str(&'a str),
+        /// A UTF-8 string ignoring ASCII case.
+        IStr(&'a str),
+        /// A UTF-8 string ignoring case by Unicode simple case folding.
+        FoldStr(&'a str),
+        U8Slice(&'a [u8]),
+        /// A byte in the inclusive range like `0x30..=0x39`.
+        ByteRange(u8, u8),
+        /// A byte in the set.
+        ByteSet(&'a ByteSet),
+        /// Input matched by the predicate.
+        Predicate(&'a Predicate<'a, [u8]>),
+        /// An unsigned LEB128 integer which fits in `u64`.
+        Uleb128,
+        /// A signed LEB128 integer which fits in `i64`.
+        Sleb128,
+        /// A MIDI variable-length quantity of at most 4 bytes.
+        Vlq,
+        /// A UTF-8 char in the inclusive range like `'a'..='z'`.
+        CharRange(char, char),
+        /// A UTF-8 char in the string.
+        CharSet(&'a str),
+        /// A UTF-8 char with the Unicode `Alphabetic` property.
+        Alphabetic,
+        /// A UTF-8 char with the Unicode `Numeric` property.
+        Numeric,
+        /// A UTF-8 char with the Unicode `White_Space` property.
+        Whitespace,
+        /// A UTF-8 char with the Unicode `XID_Start` property.
+        XidStart,
+        /// A UTF-8 char with the Unicode `XID_Continue` property.
+        XidContinue,
+        // Big Endian
+        BEf32(f32),
+        BEf64(f64),
+        BEu8(u8),
+        BEi8(i8),
+        BEu16(u16),
+        BEi16(i16),
+        BEu32(u32),
+        BEi32(i32),
+        BEu64(u64),
+        BEi64(i64),
+        BEu128(u128),
+        BEi128(i128),
+        BEusize(usize),
+        BEisize(isize),
+        // Little Endian
+        LEf32(f32),
+        LEf64(f64),
+        LEu8(u8),
+        LEi8(i8),
+        LEu16(u16),
+        LEi16(i16),
+        LEu32(u32),
+        LEi32(i32),
+        LEu64(u64),
+        LEi64(i64),
+        LEu128(u128),
+        LEi128(i128),
+        LEusize(usize),
+        LEisize(isize),
+    }
+    // Type: Big Endian and Little Endian with a range of values, then with a mask and a value
+    u8: BEu8Range, LEu8Range, BEu8Mask, LEu8Mask;
+    i8: BEi8Range, LEi8Range, BEi8Mask, LEi8Mask;
+    u16: BEu16Range, LEu16Range, BEu16Mask, LEu16Mask;
+    i16: BEi16Range, LEi16Range, BEi16Mask, LEi16Mask;
+    u32: BEu32Range, LEu32Range, BEu32Mask, LEu32Mask;
+    i32: BEi32Range, LEi32Range, BEi32Mask, LEi32Mask;
+    u64: BEu64Range, LEu64Range, BEu64Mask, LEu64Mask;
+    i64: BEi64Range, LEi64Range, BEi64Mask, LEi64Mask;
+    u128: BEu128Range, LEu128Range, BEu128Mask, LEu128Mask;
+    i128: BEi128Range, LEi128Range, BEi128Mask, LEi128Mask;
+    usize: BEusizeRange, LEusizeRange, BEusizeMask, LEusizeMask;
+    isize: BEisizeRange, LEisizeRange, BEisizeMask, LEisizeMask;
+}
+
+impl U8SliceTerminal<'_> {
+    /// Returns true if `c` is in the char class.
+    ///
+    /// Terminal symbols other than char classes contain no char.
+    fn contains_char(&self, c: char) -> bool {
+        match self {
+            Self::CharRange(start, end) => (*start..=*end).contains(&c),
+            Self::CharSet(s) => s.contains(c),
+            Self::Alphabetic => c.is_alphabetic(),
+            Self::Numeric => c.is_numeric(),
+            Self::Whitespace => c.is_whitespace(),
+            Self::XidStart => c.is_xid_start(),
+            Self::XidContinue => c.is_xid_continue(),
+            Self::Char(_)
+            | Self::Str(_)
+            | Self::IStr(_)
+            | Self::FoldStr(_)
+            | Self::U8Slice(_)
+            | Self::ByteRange(..)
+            | Self::ByteSet(_)
+            | Self::Predicate(_)
+            | Self::Uleb128
+            | Self::Sleb128
+            | Self::Vlq
+            | Self::BEf32(_)
+            | Self::BEf64(_)
+            | Self::BEu8(_)
+            | Self::BEi8(_)
+            | Self::BEu16(_)
+            | Self::BEi16(_)
+            | Self::BEu32(_)
+            | Self::BEi32(_)
+            | Self::BEu64(_)
+            | Self::BEi64(_)
+            | Self::BEu128(_)
+            | Self::BEi128(_)
+            | Self::BEusize(_)
+            | Self::BEisize(_)
+            | Self::LEf32(_)
+            | Self::LEf64(_)
+            | Self::LEu8(_)
+            | Self::LEi8(_)
+            | Self::LEu16(_)
+            | Self::LEi16(_)
+            | Self::LEu32(_)
+            | Self::LEi32(_)
+            | Self::LEu64(_)
+            | Self::LEi64(_)
+            | Self::LEu128(_)
+            | Self::LEi128(_)
+            | Self::LEusize(_)
+            | Self::LEisize(_)
+            | checked_int!() => false,
+        }
+    }
+}
+
+/// Returns the first UTF-8 char of `bytes`.
+fn first_char(bytes: &[u8]) -> Option<char> {
+    utf8_prefix(&bytes[..bytes.len().min(4)]).chars().next()
+}
+
+/// Returns the longest prefix of `bytes` which is valid UTF-8.
+///
+/// An invalid or incomplete char ends the prefix.
+fn utf8_prefix(bytes: &[u8]) -> &str {
+    match str::from_utf8(bytes) {
+        Ok(s) => s,
+        Err(e) => str::from_utf8(&bytes[..e.valid_up_to()]).unwrap_or_default(),
+    }
 }
 
 impl From<char> for U8SliceTerminal<'_> {
@@ -60,34 +254,154 @@ impl<'a> From<&'a [u8]> for U8SliceTerminal<'a> {
     }
 }
 
+/// Shows `Char`, `Str`, char classes, byte classes and variable-length integers as literals, and the others as `Debug`.
+impl fmt::Display for U8SliceTerminal<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Char(c) => write!(f, "{:?}", c),
+            Self::Str(s) => write!(f, "{:?}", s),
+            Self::IStr(s) => write!(f, "{:?} ignoring ASCII case", s),
+            Self::FoldStr(s) => write!(f, "{:?} ignoring case", s),
+            Self::ByteRange(start, end) => write!(f, "{:#04x}..={:#04x}", start, end),
+            Self::ByteSet(set) => write!(f, "one of {:?}", set),
+            Self::Predicate(_) => write!(f, "predicate"),
+            Self::Uleb128 => write!(f, "ULEB128"),
+            Self::Sleb128 => write!(f, "SLEB128"),
+            Self::Vlq => write!(f, "VLQ"),
+            Self::CharRange(start, end) => write!(f, "{:?}..={:?}", start, end),
+            Self::CharSet(s) => write!(f, "one of {:?}", s),
+            Self::Alphabetic => write!(f, "alphabetic"),
+            Self::Numeric => write!(f, "numeric"),
+            Self::Whitespace => write!(f, "whitespace"),
+            Self::XidStart => write!(f, "XID_Start"),
+            Self::XidContinue => write!(f, "XID_Continue"),
+            Self::U8Slice(_)
+            | Self::BEf32(_)
+            | Self::BEf64(_)
+            | Self::BEu8(_)
+            | Self::BEi8(_)
+            | Self::BEu16(_)
+            | Self::BEi16(_)
+            | Self::BEu32(_)
+            | Self::BEi32(_)
+            | Self::BEu64(_)
+            | Self::BEi64(_)
+            | Self::BEu128(_)
+            | Self::BEi128(_)
+            | Self::BEusize(_)
+            | Self::BEisize(_)
+            | Self::LEf32(_)
+            | Self::LEf64(_)
+            | Self::LEu8(_)
+            | Self::LEi8(_)
+            | Self::LEu16(_)
+            | Self::LEi16(_)
+            | Self::LEu32(_)
+            | Self::LEi32(_)
+            | Self::LEu64(_)
+            | Self::LEi64(_)
+            | Self::LEu128(_)
+            | Self::LEi128(_)
+            | Self::LEusize(_)
+            | Self::LEisize(_)
+            | checked_int!() => write!(f, "{:?}", self),
+        }
+    }
+}
+
 impl<'a, V, P, L, O> Terminal<'a, [u8], V, StartAndLenSpan<P, L>, P, O> for U8SliceTerminal<'a>
 where
     P: Start<[u8], L>,
     L: Len<[u8], P>,
 {
     fn eval(&self, input: &'a [u8], pos: P, max_pos: &P) -> StartAndLenResult<V, P, L, O> {
-        let eval_from = |len: usize, slice: &[u8]| {
+        let eval_len = |len: usize, f: &dyn Fn(&[u8]) -> bool| {
             let start = pos.clone();
-            let pos: usize = P::into_usize(pos, input);
+            let pos: usize = P::into_usize(pos.clone(), input);
             let span = StartAndLenSpan::from_lo_len(start, len, input);
             let hi = span.hi(input);
             let ast = AST::from_leaf(Metasymbol::Omit.into(), span);
             if &hi <= max_pos {
                 if let Some(s) = input.get(pos..pos + len) {
-                    if s == slice {
+                    if f(s) {
                         return Ok(ast);
                     }
                 }
             }
             Err(ast)
         };
+        let eval_from = |len: usize, slice: &[u8]| eval_len(len, &|s| s == slice);
+
+        let eval_byte = |f: &dyn Fn(u8) -> bool| {
+            let span = StartAndLenSpan::from_lo_len(pos.clone(), 1, input);
+            let hi = span.hi(input);
+            let ast = AST::from_leaf(Metasymbol::Omit.into(), span);
+            match input.get(P::into_usize(pos.clone(), input)) {
+                Some(b) if &hi <= max_pos && f(*b) => Ok(ast),
+                _ => Err(ast),
+            }
+        };
 
         match self {
             // TODO: create test
             Self::Char(c) => eval_from(c.len_utf8(), c.to_string().as_bytes()),
             // TODO: create test
             Self::Str(s) => eval_from(s.len(), s.as_bytes()),
+            Self::IStr(s) => eval_len(s.len(), &|t| t.eq_ignore_ascii_case(s.as_bytes())),
+            Self::FoldStr(s) => {
+                // Each char of `s` matches a single char of at most 4 bytes, so only those bytes are decoded.
+                let max_len = 4 * s.chars().count();
+                let rest = input
+                    .get(P::into_usize(pos.clone(), input)..)
+                    .map_or("", |rest| utf8_prefix(&rest[..rest.len().min(max_len)]));
+                match len_of_case_folded(rest, s) {
+                    Some(len) => eval_len(len, &|_| true),
+                    None => eval_len(s.len(), &|_| false),
+                }
+            }
             Self::U8Slice(slice) => eval_from(slice.len(), slice),
+            Self::ByteRange(start, end) => eval_byte(&|b| (*start..=*end).contains(&b)),
+            Self::ByteSet(set) => eval_byte(&|b| set.contains(b)),
+            Self::Predicate(predicate) => eval_len_of(
+                input,
+                pos,
+                max_pos,
+                |rest| predicate.len_of(rest),
+                |input, range| input.get(range),
+            ),
+            Self::Uleb128 => eval_len_of(
+                input,
+                pos,
+                max_pos,
+                |rest| varint::uleb128(rest).map(|(_, len)| len),
+                |input, range| input.get(range),
+            ),
+            Self::Sleb128 => eval_len_of(
+                input,
+                pos,
+                max_pos,
+                |rest| varint::sleb128(rest).map(|(_, len)| len),
+                |input, range| input.get(range),
+            ),
+            Self::Vlq => eval_len_of(
+                input,
+                pos,
+                max_pos,
+                |rest| varint::vlq(rest).map(|(_, len)| len),
+                |input, range| input.get(range),
+            ),
+            Self::CharRange(..)
+            | Self::CharSet(_)
+            | Self::Alphabetic
+            | Self::Numeric
+            | Self::Whitespace
+            | Self::XidStart
+            | Self::XidContinue => {
+                let c = input
+                    .get(P::into_usize(pos.clone(), input)..)
+                    .and_then(first_char);
+                eval_char(input, pos, max_pos, c, |c| self.contains_char(c))
+            }
 
             Self::BEf32(n) => eval_from(mem::size_of::<f32>(), &n.to_be_bytes()),
             Self::LEf32(n) => eval_from(mem::size_of::<f32>(), &n.to_le_bytes()),
@@ -123,6 +437,13 @@ where
             Self::LEusize(n) => eval_from(mem::size_of::<usize>(), &n.to_le_bytes()),
             Self::BEisize(n) => eval_from(mem::size_of::<isize>(), &n.to_be_bytes()),
             Self::LEisize(n) => eval_from(mem::size_of::<isize>(), &n.to_le_bytes()),
+
+            checked_int!() => {
+                let len = self
+                    .checked_int_len()
+                    .expect("integer with a range or a mask");
+                eval_len(len, &|s| self.checked_int_matches(s))
+            }
         }
     }
 }
@@ -139,4 +460,100 @@ mod tests {
         assert_eq!(c, U8SliceTerminal::Char('A'));
         assert_eq!(s, U8SliceTerminal::U8Slice(&[0, 1, 2]));
     }
+
+    #[test]
+    fn char_class() {
+        let input = "aあ".as_bytes();
+        let max_pos = input.len() as u32;
+        let eval = |terminal: U8SliceTerminal, pos: u32| {
+            let result: StartAndLenResult<(), u32, u32, ()> = terminal.eval(input, pos, &max_pos);
+            result.map(|ast| ast.span.len).map_err(|ast| ast.span.len)
+        };
+
+        assert_eq!(eval(U8SliceTerminal::CharRange('a', 'z'), 0), Ok(1));
+        assert_eq!(eval(U8SliceTerminal::CharSet("あい"), 1), Ok(3));
+        assert_eq!(eval(U8SliceTerminal::Alphabetic, 2), Err(0));
+        assert_eq!(eval(U8SliceTerminal::Alphabetic, 4), Err(0));
+
+        // Truncated by `max_pos`
+        let result: StartAndLenResult<(), u32, u32, ()> =
+            U8SliceTerminal::Alphabetic.eval(input, 1, &3);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn byte_class() {
+        const DIGITS: ByteSet = ByteSet::new().with_range(b'0', b'9');
+        let input = b"7x";
+        let max_pos = input.len() as u32;
+        let eval = |terminal: U8SliceTerminal, pos: u32| {
+            let result: StartAndLenResult<(), u32, u32, ()> =
+                terminal.eval(&input[..], pos, &max_pos);
+            result.is_ok()
+        };
+
+        assert!(eval(U8SliceTerminal::ByteRange(0x30, 0x39), 0));
+        assert!(!eval(U8SliceTerminal::ByteRange(0x30, 0x39), 1));
+        assert!(eval(U8SliceTerminal::ByteSet(&DIGITS), 0));
+        assert!(!eval(U8SliceTerminal::ByteSet(&DIGITS), 1));
+        assert!(!eval(U8SliceTerminal::ByteSet(&DIGITS), 2));
+
+        assert_eq!(
+            U8SliceTerminal::ByteRange(0x30, 0x39).to_string(),
+            "0x30..=0x39"
+        );
+        assert_eq!(
+            U8SliceTerminal::ByteSet(&ByteSet::from_bytes(b"01")).to_string(),
+            "one of {48, 49}"
+        );
+    }
+
+    #[test]
+    fn ignore_case() {
+        // KELVIN SIGN and an invalid byte
+        let input = b"Select \xe2\x84\xaa\xff";
+        let max_pos = input.len() as u32;
+        let eval = |terminal: U8SliceTerminal, pos: u32| {
+            let result: StartAndLenResult<(), u32, u32, ()> =
+                terminal.eval(&input[..], pos, &max_pos);
+            result.map(|ast| ast.span.len).map_err(|ast| ast.span.len)
+        };
+
+        assert_eq!(eval(U8SliceTerminal::IStr("SELECT"), 0), Ok(6));
+        assert_eq!(eval(U8SliceTerminal::FoldStr("SELECT"), 0), Ok(6));
+        assert_eq!(eval(U8SliceTerminal::FoldStr("k"), 7), Ok(3));
+        // Only 4 bytes are decoded, which cut KELVIN SIGN.
+        assert_eq!(eval(U8SliceTerminal::FoldStr("t"), 5), Ok(1));
+        // The invalid byte is not a char.
+        assert_eq!(eval(U8SliceTerminal::FoldStr("k\u{ff}"), 7), Err(3));
+        assert_eq!(eval(U8SliceTerminal::FoldStr("kk"), 7), Err(2));
+    }
+
+    #[test]
+    fn checked_int() {
+        let input = [0x01, 0x02, 0xff, 0xff];
+        let eval = |terminal: U8SliceTerminal, pos: u32| {
+            let result: StartAndLenResult<(), u32, u32, ()> = terminal.eval(&input[..], pos, &4);
+            result.map(|ast| ast.span.len).map_err(|ast| ast.span.len)
+        };
+
+        assert_eq!(eval(U8SliceTerminal::LEu16Range(0x0200, 0x0201), 0), Ok(2));
+        assert_eq!(eval(U8SliceTerminal::BEu16Range(0x0200, 0x0201), 0), Err(2));
+        assert_eq!(eval(U8SliceTerminal::BEu16Range(0x0100, 0x0200), 0), Ok(2));
+        assert_eq!(eval(U8SliceTerminal::LEi16Range(-1, 0), 2), Ok(2));
+        assert_eq!(eval(U8SliceTerminal::LEi16Range(-1, 0), 1), Err(2));
+        // Out of `max_pos`
+        assert_eq!(eval(U8SliceTerminal::BEu32Range(0, u32::MAX), 1), Err(4));
+
+        assert_eq!(eval(U8SliceTerminal::LEu16Mask(0x00ff, 0x0001), 0), Ok(2));
+        assert_eq!(eval(U8SliceTerminal::BEu16Mask(0x00ff, 0x0001), 0), Err(2));
+        assert_eq!(eval(U8SliceTerminal::BEu32Mask(0xffff, 0xffff), 0), Ok(4));
+        assert_eq!(eval(U8SliceTerminal::LEi16Mask(-1, -1), 2), Ok(2));
+        assert_eq!(eval(U8SliceTerminal::BEi8Mask(0x0f, 0x0f), 3), Ok(1));
+        assert_eq!(eval(U8SliceTerminal::BEi8Mask(0x0f, 0x0f), 0), Err(1));
+
+        // Bytes of a wrong length
+        assert!(!U8SliceTerminal::LEu16Range(0, u16::MAX).checked_int_matches(&[0]));
+        assert!(!U8SliceTerminal::LEu16Mask(0, 0).checked_int_matches(&[0, 0, 0]));
+    }
 }
@@ -0,0 +1,141 @@
+//! Variable-length integers
+//!
+//! Each function decodes an integer at the start of `bytes` and returns the value and the length of the encoding.
+//! It returns `None` if the encoding is truncated, overlong or out of the value type.
+//!
+//! ```
+//! use mpl::symbols::varint;
+//!
+//! assert_eq!(varint::uleb128(&[0xe5, 0x8e, 0x26]), Some((624485, 3)));
+//! assert_eq!(varint::sleb128(&[0xc0, 0xbb, 0x78]), Some((-123456, 3)));
+//! assert_eq!(varint::vlq(&[0x81, 0x00]), Some((128, 2)));
+//! ```
+
+/// Decodes an unsigned LEB128 integer, which is at most 10 bytes.
+pub fn uleb128(bytes: &[u8]) -> Option<(u64, usize)> {
+    let mut value = 0;
+    for (i, &b) in bytes.iter().enumerate().take(10) {
+        let low = u64::from(b & 0x7f);
+        // Only bit 63 is left.
+        if i == 9 && low > 1 {
+            return None;
+        }
+        value |= low << (7 * i);
+        if b & 0x80 == 0 {
+            // Overlong if the last byte has no bits.
+            if i != 0 && b == 0 {
+                return None;
+            }
+            return Some((value, i + 1));
+        }
+    }
+    None
+}
+
+/// Decodes a signed LEB128 integer, which is at most 10 bytes.
+pub fn sleb128(bytes: &[u8]) -> Option<(i64, usize)> {
+    let mut value = 0;
+    for (i, &b) in bytes.iter().enumerate().take(10) {
+        value |= i64::from(b & 0x7f) << (7 * i);
+        if b & 0x80 == 0 {
+            // Only bit 63 and its sign extension are left.
+            if i == 9 && b != 0 && b != 0x7f {
+                return None;
+            }
+            // Overlong if the last byte only extends the sign of the previous byte.
+            if i != 0 {
+                let negative = bytes[i - 1] & 0x40 != 0;
+                if (b == 0 && !negative) || (b == 0x7f && negative) {
+                    return None;
+                }
+            }
+            if b & 0x40 != 0 && i < 9 {
+                value |= -1 << (7 * (i + 1));
+            }
+            return Some((value, i + 1));
+        }
+    }
+    None
+}
+
+/// Decodes a MIDI variable-length quantity, which is at most 4 bytes.
+pub fn vlq(bytes: &[u8]) -> Option<(u32, usize)> {
+    // Overlong if the first byte has no bits.
+    if bytes.first() == Some(&0x80) {
+        return None;
+    }
+    let mut value = 0;
+    for (i, &b) in bytes.iter().enumerate().take(4) {
+        value = value << 7 | u32::from(b & 0x7f);
+        if b & 0x80 == 0 {
+            return Some((value, i + 1));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unsigned_leb128() {
+        assert_eq!(uleb128(&[0x00]), Some((0, 1)));
+        assert_eq!(uleb128(&[0x7f, 0xff]), Some((127, 1)));
+        assert_eq!(uleb128(&[0x80, 0x01]), Some((128, 2)));
+        assert_eq!(
+            uleb128(&[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01]),
+            Some((u64::MAX, 10))
+        );
+        // Truncated
+        assert_eq!(uleb128(&[0x80]), None);
+        assert_eq!(uleb128(&[]), None);
+        // Overlong
+        assert_eq!(uleb128(&[0x80, 0x00]), None);
+        // Out of u64
+        assert_eq!(
+            uleb128(&[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02]),
+            None
+        );
+    }
+
+    #[test]
+    fn signed_leb128() {
+        assert_eq!(sleb128(&[0x00]), Some((0, 1)));
+        assert_eq!(sleb128(&[0x7f]), Some((-1, 1)));
+        assert_eq!(sleb128(&[0x3f]), Some((63, 1)));
+        assert_eq!(sleb128(&[0xc0, 0x00]), Some((64, 2)));
+        assert_eq!(sleb128(&[0x80, 0x7f]), Some((-128, 2)));
+        assert_eq!(
+            sleb128(&[0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x7f]),
+            Some((i64::MIN, 10))
+        );
+        assert_eq!(
+            sleb128(&[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00]),
+            Some((i64::MAX, 10))
+        );
+        // Truncated
+        assert_eq!(sleb128(&[0xff]), None);
+        // Overlong
+        assert_eq!(sleb128(&[0x80, 0x00]), None);
+        assert_eq!(sleb128(&[0xff, 0x7f]), None);
+        // Out of i64
+        assert_eq!(
+            sleb128(&[0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x01]),
+            None
+        );
+    }
+
+    #[test]
+    fn midi_vlq() {
+        assert_eq!(vlq(&[0x00]), Some((0, 1)));
+        assert_eq!(vlq(&[0x81, 0x00]), Some((0x80, 2)));
+        assert_eq!(vlq(&[0xff, 0xff, 0xff, 0x7f]), Some((0x0fff_ffff, 4)));
+        // Truncated
+        assert_eq!(vlq(&[0x81]), None);
+        // Overlong
+        assert_eq!(vlq(&[0x80, 0x01]), None);
+        // More than 4 bytes
+        assert_eq!(vlq(&[0xff, 0xff, 0xff, 0xff, 0x7f]), None);
+    }
+}
@@ -4,19 +4,21 @@ use crate::choices::{Choice, First, Second};
 use crate::span::Spanned;
 use crate::symbols::{Equivalence, Metasymbol, TerminalSymbol};
 use std::fmt;
+use std::ops::Deref;
+use std::sync::Arc;
 
 /// Leaf Node
 pub type Leaf<O = ()> = TerminalSymbol<O>;
 /// Internal Node
-pub type Internal<V, S, O = ()> = Equivalence<(V, Option<O>), Box<Choice<AST<V, S, O>>>>;
+pub type Internal<V, S, O = ()> = Equivalence<(V, Option<O>), Children<V, S, O>>;
 
 impl<V, S, O> Internal<V, S, O> {
     pub fn from_first(value: (V, Option<O>), l: AST<V, S, O>, r: AST<V, S, O>) -> Self {
-        Equivalence::new(value, Box::new((l, r).into()))
+        Equivalence::new(value, Children::new((l, r).into()))
     }
 
     pub fn from_second(value: (V, Option<O>), e: AST<V, S, O>) -> Self {
-        Equivalence::new(value, Box::new(e.into()))
+        Equivalence::new(value, Children::new(e.into()))
     }
 
     pub fn as_first(&self) -> Option<&First<AST<V, S, O>>> {
@@ -26,13 +28,82 @@ impl<V, S, O> Internal<V, S, O> {
     pub fn as_second(&self) -> Option<&Second<AST<V, S, O>>> {
         self.equal.as_second()
     }
+}
 
+/// Since 0.3, children are shared, so taking them needs `Clone`.
+impl<V: Clone, S: Clone, O: Clone> Internal<V, S, O> {
+    /// Children shared with other ASTs are cloned.
     pub fn into_first(self) -> Option<First<AST<V, S, O>>> {
-        self.equal.into_first()
+        self.equal.into_choice().into_first()
     }
 
+    /// Children shared with other ASTs are cloned.
     pub fn into_second(self) -> Option<Second<AST<V, S, O>>> {
-        self.equal.into_second()
+        self.equal.into_choice().into_second()
+    }
+}
+
+/// Children of an internal node.
+///
+/// Children are shared with `Arc`, so cloning an AST doesn't copy its subtrees, and ASTs are `Send` and `Sync` if `V`, `S` and `O` are.
+/// They are dropped with a stack on the heap instead of native recursion, so deep ASTs don't overflow the thread stack.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Children<V, S, O = ()>(
+    // `None` only after being taken by `drop` or `into_choice`.
+    Option<SharedChoice<V, S, O>>,
+);
+
+type SharedChoice<V, S, O> = Arc<Choice<AST<V, S, O>>>;
+
+impl<V, S, O> Children<V, S, O> {
+    pub fn new(choice: Choice<AST<V, S, O>>) -> Self {
+        Self(Some(Arc::new(choice)))
+    }
+
+    /// Returns true if both are the same children shared with `Arc`.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.0, &other.0) {
+            (Some(l), Some(r)) => Arc::ptr_eq(l, r),
+            _ => false,
+        }
+    }
+}
+
+impl<V: Clone, S: Clone, O: Clone> Children<V, S, O> {
+    /// Children shared with other ASTs are cloned.
+    pub fn into_choice(mut self) -> Choice<AST<V, S, O>> {
+        Arc::unwrap_or_clone(self.0.take().expect("children"))
+    }
+}
+
+impl<V, S, O> Deref for Children<V, S, O> {
+    type Target = Choice<AST<V, S, O>>;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref().expect("children")
+    }
+}
+
+impl<V, S, O> Drop for Children<V, S, O> {
+    fn drop(&mut self) {
+        fn take<V, S, O>(ast: AST<V, S, O>, stack: &mut Vec<SharedChoice<V, S, O>>) {
+            if let Node::Internal(mut internal) = ast.node {
+                stack.extend(internal.equal.0.take());
+            }
+        }
+
+        let mut stack: Vec<_> = self.0.take().into_iter().collect();
+        while let Some(choice) = stack.pop() {
+            // Children still shared with other ASTs are dropped by the last of them.
+            match Arc::into_inner(choice) {
+                Some(Choice::First(first)) => {
+                    take(first.lhs, &mut stack);
+                    take(first.rhs, &mut stack);
+                }
+                Some(Choice::Second(second)) => take(second.0, &mut stack),
+                None => {}
+            }
+        }
     }
 }
 
@@ -143,14 +214,6 @@ impl<V, S, O> AST<V, S, O> {
         self.as_internal().and_then(|n| n.as_second())
     }
 
-    pub fn into_first(self) -> Option<First<AST<V, S, O>>> {
-        self.into_internal().and_then(|n| n.into_first())
-    }
-
-    pub fn into_second(self) -> Option<Second<AST<V, S, O>>> {
-        self.into_internal().and_then(|n| n.into_second())
-    }
-
     pub fn as_original(&self) -> Option<&O> {
         self.as_leaf().and_then(|n| n.as_original())
     }
@@ -168,6 +231,17 @@ impl<V, S, O> AST<V, S, O> {
     }
 }
 
+/// Since 0.3, children are shared, so taking them needs `Clone`.
+impl<V: Clone, S: Clone, O: Clone> AST<V, S, O> {
+    pub fn into_first(self) -> Option<First<AST<V, S, O>>> {
+        self.into_internal().and_then(|n| n.into_first())
+    }
+
+    pub fn into_second(self) -> Option<Second<AST<V, S, O>>> {
+        self.into_internal().and_then(|n| n.into_second())
+    }
+}
+
 impl<V: fmt::Debug, S, O: fmt::Debug> AST<V, S, O> {
     fn write_tree(&self, f: &mut fmt::Formatter<'_>, mut prefix: String) -> fmt::Result {
         match &self.node {
@@ -234,3 +308,16 @@ impl<O, V, S: Clone> CST<V, S, O> {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::span::StartAndLenSpan;
+
+    #[test]
+    fn send_and_sync() {
+        fn assert_send_and_sync<T: Send + Sync>() {}
+
+        assert_send_and_sync::<AST<u8, StartAndLenSpan<u32, u32>, String>>();
+    }
+}
@@ -0,0 +1,192 @@
+use mpl::input::{BitOrder, BitSlice};
+use mpl::output::Output;
+use mpl::parser::Parser;
+use mpl::rules::{RightRule, RightRuleKind, Rules};
+use mpl::span::{Len, Start, StartAndLenSpan};
+use mpl::symbols::{BitsTerminal, Variable};
+use mpl::trees::AST;
+use std::collections::HashMap;
+
+mod common;
+
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+enum BitsVariable {
+    // MPEG audio frame header
+    FrameHeader,
+    Version,
+    Layer,
+    Protection,
+    // DEFLATE block header
+    BlockHeader,
+    BlockType,
+}
+
+impl Variable for BitsVariable {}
+
+/// Span in bits.
+type BitSpan = StartAndLenSpan<u32, u8>;
+
+struct BitsParser;
+
+impl<'i, 'a, V, P, L, R, O>
+    Parser<'i, BitSlice<'a>, BitsTerminal, V, StartAndLenSpan<P, L>, P, R, O> for BitsParser
+where
+    V: Variable,
+    P: Start<BitSlice<'a>, L>,
+    L: Len<BitSlice<'a>, P>,
+    R: Rules<BitsTerminal, V>,
+    O: Output<'i, BitSlice<'a>, V, StartAndLenSpan<P, L>>,
+{
+}
+
+/// ```
+/// FrameHeader = { Msb(11, 0x7ff) } Version / f
+/// Version = { Field(2) } Layer / f
+/// // Layer III
+/// Layer = { Msb(2, 0b01) } Protection / f
+/// Protection = ? () / f
+/// ```
+#[test]
+fn msb_first() {
+    let parser = BitsParser;
+    let mut rules = HashMap::new();
+
+    rules.insert(
+        BitsVariable::FrameHeader,
+        RightRule::from_right_rule_kind(
+            (
+                RightRuleKind::T(BitsTerminal::Msb(11, 0x7ff)),
+                RightRuleKind::V(BitsVariable::Version),
+            ),
+            RightRuleKind::Failure,
+        ),
+    );
+    rules.insert(
+        BitsVariable::Version,
+        RightRule::from_right_rule_kind(
+            (
+                RightRuleKind::T(BitsTerminal::Field(2)),
+                RightRuleKind::V(BitsVariable::Layer),
+            ),
+            RightRuleKind::Failure,
+        ),
+    );
+    rules.insert(
+        BitsVariable::Layer,
+        RightRule::from_right_rule_kind(
+            (
+                RightRuleKind::T(BitsTerminal::Msb(2, 0b01)),
+                RightRuleKind::V(BitsVariable::Protection),
+            ),
+            RightRuleKind::Failure,
+        ),
+    );
+    rules.insert(
+        BitsVariable::Protection,
+        RightRule::from_right_rule_kind(
+            (RightRuleKind::Any(1), RightRuleKind::Empty),
+            RightRuleKind::Failure,
+        ),
+    );
+
+    // MPEG-1 Layer III without CRC
+    let input = BitSlice::new(&[0xff, 0xfb], BitOrder::MsbFirst);
+    let all_of_the_span = BitSpan::from_start_len(0, 16);
+    let result: Result<AST<BitsVariable, BitSpan>, _> = common::parse(
+        &parser,
+        &input,
+        &rules,
+        &BitsVariable::FrameHeader,
+        &all_of_the_span,
+    );
+    let ast = result.unwrap();
+    let version = &ast.as_first().unwrap().rhs;
+    assert_eq!(version.span, BitSpan::from_start_len(11, 5));
+
+    // Layer I
+    let input = BitSlice::new(&[0xff, 0xff], BitOrder::MsbFirst);
+    let all_of_the_span = BitSpan::from_start_len(0, 16);
+    let result: Result<AST<BitsVariable, BitSpan>, _> = common::parse(
+        &parser,
+        &input,
+        &rules,
+        &BitsVariable::FrameHeader,
+        &all_of_the_span,
+    );
+    let error = result.unwrap_err();
+    assert!(error.kind.is_failure());
+    assert_eq!(
+        error.farthest_failure.to_string(),
+        "expected 0b1 in 2 bits MSB first at 13"
+    );
+
+    // Truncated
+    let all_of_the_span = BitSpan::from_start_len(0, 12);
+    let result: Result<AST<BitsVariable, BitSpan>, _> = common::parse(
+        &parser,
+        &input,
+        &rules,
+        &BitsVariable::FrameHeader,
+        &all_of_the_span,
+    );
+    let error = result.unwrap_err();
+    assert!(error.kind.is_failure());
+}
+
+/// ```
+/// // Final block
+/// BlockHeader = { Lsb(1, 1) } BlockType / f
+/// // Dynamic Huffman codes
+/// BlockType = { Lsb(2, 0b10) } () / f
+/// ```
+#[test]
+fn lsb_first() {
+    let parser = BitsParser;
+    let mut rules = HashMap::new();
+
+    rules.insert(
+        BitsVariable::BlockHeader,
+        RightRule::from_right_rule_kind(
+            (
+                RightRuleKind::T(BitsTerminal::Lsb(1, 1)),
+                RightRuleKind::V(BitsVariable::BlockType),
+            ),
+            RightRuleKind::Failure,
+        ),
+    );
+    rules.insert(
+        BitsVariable::BlockType,
+        RightRule::from_right_rule_kind(
+            (
+                RightRuleKind::T(BitsTerminal::Lsb(2, 0b10)),
+                RightRuleKind::Empty,
+            ),
+            RightRuleKind::Failure,
+        ),
+    );
+
+    let input = BitSlice::new(&[0b1111_1101], BitOrder::LsbFirst);
+    let all_of_the_span = BitSpan::from_start_len(0, 3);
+    let result: Result<AST<BitsVariable, BitSpan>, _> = common::parse(
+        &parser,
+        &input,
+        &rules,
+        &BitsVariable::BlockHeader,
+        &all_of_the_span,
+    );
+    let ast = result.unwrap();
+    assert_eq!(ast.span, BitSpan::from_start_len(0, 3));
+
+    // Fixed Huffman codes
+    let input = BitSlice::new(&[0b0000_0011], BitOrder::LsbFirst);
+    let all_of_the_span = BitSpan::from_start_len(0, 3);
+    let result: Result<AST<BitsVariable, BitSpan>, _> = common::parse(
+        &parser,
+        &input,
+        &rules,
+        &BitsVariable::BlockHeader,
+        &all_of_the_span,
+    );
+    let error = result.unwrap_err();
+    assert_eq!(error.farthest_failure.pos, Some(1));
+}
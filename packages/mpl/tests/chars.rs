@@ -0,0 +1,210 @@
+use mpl::output::Output;
+use mpl::parser::Parser;
+use mpl::rules::{RightRule, RightRuleKind, Rules};
+use mpl::span::{Len, Start, StartAndLenSpan};
+use mpl::symbols::{StrTerminal, Variable};
+use mpl::trees::AST;
+use std::collections::HashMap;
+
+mod common;
+
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+enum CharsVariable {
+    Chars,
+    Greeting,
+    Identifier,
+    IdentifierContinue,
+}
+
+impl Variable for CharsVariable {}
+
+type CharsSpan = StartAndLenSpan<u32, u32>;
+
+struct CharsParser;
+
+impl<'i, V, P, L, R, O> Parser<'i, str, StrTerminal<'i>, V, StartAndLenSpan<P, L>, P, R, O>
+    for CharsParser
+where
+    V: Variable,
+    P: Start<str, L>,
+    L: Len<str, P>,
+    R: Rules<StrTerminal<'i>, V>,
+    O: Output<'i, str, V, StartAndLenSpan<P, L>>,
+{
+}
+
+/// ```
+/// Chars = ? Chars / ()
+/// Greeting = ?? '!' / f
+/// ```
+#[test]
+fn any_char() {
+    let parser = CharsParser;
+    let mut rules = HashMap::new();
+
+    rules.insert(
+        CharsVariable::Chars,
+        RightRule::from_right_rule_kind(
+            (
+                RightRuleKind::Any(1),
+                RightRuleKind::V(CharsVariable::Chars),
+            ),
+            RightRuleKind::Empty,
+        ),
+    );
+    rules.insert(
+        CharsVariable::Greeting,
+        RightRule::from_right_rule_kind(
+            (
+                RightRuleKind::Any(2),
+                RightRuleKind::T(StrTerminal::Char('!')),
+            ),
+            RightRuleKind::Failure,
+        ),
+    );
+
+    let parse = |input: &'static str, start_variable| -> Result<AST<CharsVariable, CharsSpan>, _> {
+        let all_of_the_span = CharsSpan::from_start_len(0, input.len() as u32);
+        common::parse(&parser, input, &rules, start_variable, &all_of_the_span)
+    };
+
+    let ast = parse("aあ🦀", &CharsVariable::Chars).unwrap();
+    let first = ast.as_first().unwrap();
+    assert_eq!(first.lhs.span, CharsSpan::from_start_len(0, 1));
+    let first = first.rhs.as_first().unwrap();
+    assert_eq!(first.lhs.span, CharsSpan::from_start_len(1, 3));
+    let first = first.rhs.as_first().unwrap();
+    assert_eq!(first.lhs.span, CharsSpan::from_start_len(4, 4));
+
+    // `??` is 2 chars, not 2 bytes.
+    let ast = parse("やあ!", &CharsVariable::Greeting).unwrap();
+    assert_eq!(
+        ast.as_first().unwrap().lhs.span,
+        CharsSpan::from_start_len(0, 6)
+    );
+
+    // Only 1 char is left, although there are 3 bytes.
+    let error = parse("や", &CharsVariable::Greeting).unwrap_err();
+    assert!(error.kind.is_failure());
+    assert_eq!(error.farthest_failure.to_string(), "expected ?? at 0");
+}
+
+/// ```
+/// Identifier = XID_Start IdentifierContinue / f
+/// IdentifierContinue = XID_Continue IdentifierContinue / ()
+/// ```
+#[test]
+fn char_class() {
+    let parser = CharsParser;
+    let mut rules = HashMap::new();
+
+    rules.insert(
+        CharsVariable::Identifier,
+        RightRule::from_right_rule_kind(
+            (
+                RightRuleKind::T(StrTerminal::XidStart),
+                RightRuleKind::V(CharsVariable::IdentifierContinue),
+            ),
+            RightRuleKind::Failure,
+        ),
+    );
+    rules.insert(
+        CharsVariable::IdentifierContinue,
+        RightRule::from_right_rule_kind(
+            (
+                RightRuleKind::T(StrTerminal::XidContinue),
+                RightRuleKind::V(CharsVariable::IdentifierContinue),
+            ),
+            RightRuleKind::Empty,
+        ),
+    );
+
+    let parse = |input: &'static str| -> Result<AST<CharsVariable, CharsSpan>, _> {
+        let all_of_the_span = CharsSpan::from_start_len(0, input.len() as u32);
+        common::parse(
+            &parser,
+            input,
+            &rules,
+            &CharsVariable::Identifier,
+            &all_of_the_span,
+        )
+    };
+
+    assert!(parse("変数_1").is_ok());
+
+    let error = parse("1変数").unwrap_err();
+    assert_eq!(
+        error.farthest_failure.to_string(),
+        "expected XID_Start at 0"
+    );
+
+    let error = parse("a-b").unwrap_err();
+    assert!(error.kind.is_partial_match());
+    assert_eq!(
+        error.farthest_failure.to_string(),
+        "expected XID_Continue at 1"
+    );
+}
+
+/// ```
+/// Chars = ? Chars / ()
+/// Greeting = ?? '!' / f
+/// ```
+#[cfg(feature = "grapheme")]
+#[test]
+fn any_grapheme() {
+    use mpl::input::Graphemes;
+
+    struct GraphemesParser;
+
+    impl<'i, V, P, L, R, O>
+        Parser<'i, Graphemes<'i>, StrTerminal<'i>, V, StartAndLenSpan<P, L>, P, R, O>
+        for GraphemesParser
+    where
+        V: Variable,
+        P: Start<Graphemes<'i>, L> + Start<str, L>,
+        L: Len<Graphemes<'i>, P> + Len<str, P>,
+        R: Rules<StrTerminal<'i>, V>,
+        O: Output<'i, Graphemes<'i>, V, StartAndLenSpan<P, L>>,
+    {
+    }
+
+    let mut rules = HashMap::new();
+
+    rules.insert(
+        CharsVariable::Chars,
+        RightRule::from_right_rule_kind(
+            (
+                RightRuleKind::Any(1),
+                RightRuleKind::V(CharsVariable::Chars),
+            ),
+            RightRuleKind::Empty,
+        ),
+    );
+    rules.insert(
+        CharsVariable::Greeting,
+        RightRule::from_right_rule_kind(
+            (
+                RightRuleKind::Any(2),
+                RightRuleKind::T(StrTerminal::Char('!')),
+            ),
+            RightRuleKind::Failure,
+        ),
+    );
+
+    // 'e' and a combining acute accent, then '!'
+    let input = &Graphemes::new("e\u{301}!");
+    let all_of_the_span = CharsSpan::from_start_len(0, 4);
+
+    let result: Result<AST<CharsVariable, CharsSpan>, _> =
+        GraphemesParser.parse(input, &rules, &CharsVariable::Greeting, &all_of_the_span);
+    assert!(result.is_err());
+
+    let ast: AST<CharsVariable, CharsSpan> = GraphemesParser
+        .parse(input, &rules, &CharsVariable::Chars, &all_of_the_span)
+        .unwrap();
+    assert_eq!(
+        ast.as_first().unwrap().lhs.span,
+        CharsSpan::from_start_len(0, 3)
+    );
+}
@@ -0,0 +1,40 @@
+use mpl::input::Input;
+use mpl::output::Output;
+use mpl::parser::{ParseResult, Parser};
+use mpl::position::Position;
+use mpl::rules::Rules;
+use mpl::span::Span;
+use mpl::symbols::{Terminal, Variable};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+/// Parses `input` by `parse`, and checks that `parse_memoized` and `parse_iterative` return the same result.
+pub fn parse<'i, Pa, I, T, V, S, P, R, O>(
+    parser: &Pa,
+    input: &'i I,
+    rules: &R,
+    start_variable: &V,
+    all_of_the_span: &S,
+) -> ParseResult<T, V, S, P, O>
+where
+    Pa: Parser<'i, I, T, V, S, P, R, O>,
+    I: Input + ?Sized,
+    T: Terminal<'i, I, V, S, P, O> + Clone + Debug + PartialEq,
+    V: Variable + Debug + Eq + Hash,
+    S: Span<I, P> + Debug + PartialEq,
+    P: Position + Debug + Eq + Hash,
+    R: Rules<T, V>,
+    O: Output<'i, I, V, S> + Clone + Debug + PartialEq,
+{
+    let result = parser.parse(input, rules, start_variable, all_of_the_span);
+    assert_eq!(
+        result,
+        parser.parse_memoized(input, rules, start_variable, all_of_the_span)
+    );
+    assert_eq!(
+        result,
+        parser.parse_iterative(input, rules, start_variable, all_of_the_span)
+    );
+
+    result
+}
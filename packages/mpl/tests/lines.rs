@@ -0,0 +1,205 @@
+use mpl::choices::Choice;
+use mpl::output::Output;
+use mpl::parser::Parser;
+use mpl::rules::{RightRule, RightRuleKind, Rules};
+use mpl::span::{Len, Span, Start, StartAndLenSpan};
+use mpl::symbols::{Metasymbol, StrTerminal, TerminalSymbol, Variable};
+use mpl::trees::{AST, CST};
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+enum LinesVariable {
+    Lines,
+    Line,
+    OmittedLines,
+}
+
+impl Variable for LinesVariable {}
+
+type LinesSpan = StartAndLenSpan<u32, u32>;
+
+struct LinesParser;
+
+impl<'i, V, P, L, R, O> Parser<'i, str, StrTerminal<'i>, V, StartAndLenSpan<P, L>, P, R, O>
+    for LinesParser
+where
+    V: Variable,
+    P: Start<str, L>,
+    L: Len<str, P>,
+    R: Rules<StrTerminal<'i>, V>,
+    O: Output<'i, str, V, StartAndLenSpan<P, L>>,
+{
+}
+
+/// Number of lines.
+///
+/// `Lines` is output as a leaf so that the AST doesn't get as deep as the input.
+impl<'i> Output<'i, str, LinesVariable, LinesSpan> for usize {
+    fn output_ast(
+        _input: &'i str,
+        cst: CST<LinesVariable, LinesSpan, Self>,
+    ) -> AST<LinesVariable, LinesSpan, Self> {
+        match cst.node.value {
+            LinesVariable::Lines => {
+                let span = cst.span.clone();
+                // The last `Lines` is `()`.
+                let n = cst.into_first().unwrap().rhs.into_original().unwrap_or(0) + 1;
+                AST::from_leaf(TerminalSymbol::from_original(n), span)
+            }
+            LinesVariable::Line | LinesVariable::OmittedLines => AST::from_cst(cst),
+        }
+    }
+}
+
+/// ```
+/// Lines = Line Lines / ()
+/// Line = "The quick brown fox jumps over the lazy dog." "\n" / f
+/// OmittedLines = _ Lines / f
+/// ```
+fn lines_rules() -> HashMap<LinesVariable, RightRule<StrTerminal<'static>, LinesVariable>> {
+    let mut rules = HashMap::new();
+
+    rules.insert(
+        LinesVariable::Lines,
+        RightRule::from_right_rule_kind(
+            (
+                RightRuleKind::V(LinesVariable::Line),
+                RightRuleKind::V(LinesVariable::Lines),
+            ),
+            RightRuleKind::Empty,
+        ),
+    );
+    rules.insert(
+        LinesVariable::Line,
+        RightRule::from_right_rule_kind(
+            (
+                RightRuleKind::T(StrTerminal::Str(
+                    "The quick brown fox jumps over the lazy dog.",
+                )),
+                RightRuleKind::T(StrTerminal::Char('\n')),
+            ),
+            RightRuleKind::Failure,
+        ),
+    );
+    rules.insert(
+        LinesVariable::OmittedLines,
+        RightRule::from_right_rule_kind(
+            (RightRuleKind::Omit, RightRuleKind::V(LinesVariable::Lines)),
+            RightRuleKind::Failure,
+        ),
+    );
+
+    rules
+}
+
+fn count_nodes<O>(ast: &AST<LinesVariable, LinesSpan, O>) -> usize {
+    match ast.as_internal() {
+        Some(internal) => match &*internal.equal {
+            Choice::First(first) => 1 + count_nodes(&first.lhs) + count_nodes(&first.rhs),
+            Choice::Second(second) => 1 + count_nodes(&second.0),
+        },
+        None => 1,
+    }
+}
+
+#[test]
+fn small_lines() {
+    let parser = LinesParser;
+    let rules = lines_rules();
+
+    let input = "The quick brown fox jumps over the lazy dog.\n".repeat(3);
+    let input: &str = &input;
+    let all_of_the_span = LinesSpan::from_lo_hi(0, input.len() as u32, input);
+
+    let result = parser.parse(input, &rules, &LinesVariable::Lines, &all_of_the_span);
+    assert_eq!(
+        result,
+        parser.parse_iterative(input, &rules, &LinesVariable::Lines, &all_of_the_span)
+    );
+    assert_eq!(result.unwrap().into_original(), Some(3));
+
+    // Without output
+    let result: Result<AST<LinesVariable, LinesSpan>, _> =
+        parser.parse(input, &rules, &LinesVariable::Lines, &all_of_the_span);
+    assert_eq!(
+        result,
+        parser.parse_iterative(input, &rules, &LinesVariable::Lines, &all_of_the_span)
+    );
+
+    // Partial match
+    let input = "The quick brown fox jumps over the lazy dog.\nThe quick";
+    let all_of_the_span = LinesSpan::from_lo_hi(0, input.len() as u32, input);
+
+    let result: Result<AST<LinesVariable, LinesSpan, usize>, _> =
+        parser.parse(input, &rules, &LinesVariable::Lines, &all_of_the_span);
+    assert_eq!(
+        result,
+        parser.parse_iterative(input, &rules, &LinesVariable::Lines, &all_of_the_span)
+    );
+    assert!(result.unwrap_err().kind.is_partial_match());
+}
+
+#[test]
+fn omitted_lines() {
+    let parser = LinesParser;
+    let rules = lines_rules();
+
+    let input = "The quick brown fox jumps over the lazy dog.\n".repeat(3);
+    let input: &str = &input;
+    let all_of_the_span = LinesSpan::from_lo_hi(0, input.len() as u32, input);
+
+    let lines: AST<LinesVariable, LinesSpan> = parser
+        .parse(input, &rules, &LinesVariable::Lines, &all_of_the_span)
+        .unwrap();
+    let result = parser.parse(
+        input,
+        &rules,
+        &LinesVariable::OmittedLines,
+        &all_of_the_span,
+    );
+    assert_eq!(
+        result,
+        parser.parse_iterative(
+            input,
+            &rules,
+            &LinesVariable::OmittedLines,
+            &all_of_the_span
+        )
+    );
+    let omitted_lines: AST<LinesVariable, LinesSpan> = result.unwrap();
+
+    // Same span, but the subtree of `Lines` is omitted.
+    assert_eq!(omitted_lines.span, lines.span);
+    assert_eq!(
+        omitted_lines.as_second().unwrap().0.as_metasymbol(),
+        Some(&Metasymbol::Omit)
+    );
+    assert_eq!(count_nodes(&lines), 13);
+    assert_eq!(count_nodes(&omitted_lines), 2);
+}
+
+/// About 4.5 MB of input recurses 100,000 times through `Lines`, which overflows the stack with [`Parser::parse`].
+#[test]
+fn many_lines() {
+    let parser = LinesParser;
+    let rules = lines_rules();
+
+    let input = "The quick brown fox jumps over the lazy dog.\n".repeat(100_000);
+    let input: &str = &input;
+    let all_of_the_span = LinesSpan::from_lo_hi(0, input.len() as u32, input);
+
+    let result: Result<AST<LinesVariable, LinesSpan, usize>, _> =
+        parser.parse_iterative(input, &rules, &LinesVariable::Lines, &all_of_the_span);
+    let ast = result.unwrap();
+
+    assert_eq!(ast.span, all_of_the_span);
+    assert_eq!(ast.into_original(), Some(100_000));
+
+    // Without output, the AST is as deep as the input, but is dropped without recursion.
+    let result: Result<AST<LinesVariable, LinesSpan>, _> =
+        parser.parse_iterative(input, &rules, &LinesVariable::Lines, &all_of_the_span);
+    let ast = result.unwrap();
+
+    assert_eq!(ast.span, all_of_the_span);
+    drop(ast);
+}
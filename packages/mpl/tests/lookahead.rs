@@ -0,0 +1,248 @@
+use mpl::error::ParseErrorKind;
+use mpl::output::Output;
+use mpl::parser::Parser;
+use mpl::rules::{RightRule, RightRuleKind, Rules};
+use mpl::span::{Len, Start, StartAndLenSpan};
+use mpl::symbols::{Metasymbol, StrTerminal, Variable};
+use mpl::trees::AST;
+use std::collections::HashMap;
+
+mod common;
+
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+enum KeywordVariable {
+    If,
+    EndOfKeyword,
+    Peek,
+    Alone,
+    Checked,
+    Check,
+    Body,
+    Letter,
+}
+
+impl Variable for KeywordVariable {}
+
+type KeywordSpan = StartAndLenSpan<u32, u32>;
+
+struct KeywordParser;
+
+impl<'i, V, P, L, R, O> Parser<'i, str, StrTerminal<'i>, V, StartAndLenSpan<P, L>, P, R, O>
+    for KeywordParser
+where
+    V: Variable,
+    P: Start<str, L>,
+    L: Len<str, P>,
+    R: Rules<StrTerminal<'i>, V>,
+    O: Output<'i, str, V, StartAndLenSpan<P, L>>,
+{
+}
+
+/// ```
+/// If = "if" EndOfKeyword / f
+/// EndOfKeyword = ! 'f' / f
+/// ```
+#[test]
+fn not_predicate() {
+    let parser = KeywordParser;
+    let mut rules = HashMap::new();
+
+    rules.insert(
+        KeywordVariable::If,
+        RightRule::from_right_rule_kind(
+            (
+                RightRuleKind::T(StrTerminal::Str("if")),
+                RightRuleKind::V(KeywordVariable::EndOfKeyword),
+            ),
+            RightRuleKind::Failure,
+        ),
+    );
+    rules.insert(
+        KeywordVariable::EndOfKeyword,
+        RightRule::from_right_rule_kind(
+            (RightRuleKind::Not, RightRuleKind::T(StrTerminal::Char('f'))),
+            RightRuleKind::Failure,
+        ),
+    );
+
+    let input = "if";
+    let all_of_the_span = KeywordSpan::from_start_len(0, input.len() as u32);
+    let result: Result<AST<KeywordVariable, KeywordSpan>, _> = common::parse(
+        &parser,
+        input,
+        &rules,
+        &KeywordVariable::If,
+        &all_of_the_span,
+    );
+    let ast = result.unwrap();
+    let end_of_keyword = &ast.as_first().unwrap().rhs;
+    assert_eq!(end_of_keyword.span, KeywordSpan::from_start_len(2, 0));
+    assert_eq!(
+        end_of_keyword.as_first().unwrap().lhs.as_metasymbol(),
+        Some(&Metasymbol::Not)
+    );
+
+    // `iff` is not the keyword `if`.
+    let input = "iff";
+    let all_of_the_span = KeywordSpan::from_start_len(0, input.len() as u32);
+    let result: Result<AST<KeywordVariable, KeywordSpan>, _> = common::parse(
+        &parser,
+        input,
+        &rules,
+        &KeywordVariable::If,
+        &all_of_the_span,
+    );
+    let error = result.unwrap_err();
+    assert!(error.kind.is_failure());
+    // 'f' matched inside `!`, so nothing is expected.
+    assert_eq!(error.farthest_failure.pos, None);
+}
+
+/// ```
+/// Peek = & "if" / f
+/// ```
+#[test]
+fn and_predicate() {
+    let parser = KeywordParser;
+    let mut rules = HashMap::new();
+
+    rules.insert(
+        KeywordVariable::Peek,
+        RightRule::from_right_rule_kind(
+            (RightRuleKind::And, RightRuleKind::T(StrTerminal::Str("if"))),
+            RightRuleKind::Failure,
+        ),
+    );
+
+    // `&` doesn't consume "if".
+    let input = "if";
+    let all_of_the_span = KeywordSpan::from_start_len(0, input.len() as u32);
+    let result: Result<AST<KeywordVariable, KeywordSpan>, _> = common::parse(
+        &parser,
+        input,
+        &rules,
+        &KeywordVariable::Peek,
+        &all_of_the_span,
+    );
+    match result.unwrap_err().kind {
+        ParseErrorKind::PartialMatch { ast, hi } => {
+            assert_eq!(hi, 0);
+            assert_eq!(
+                ast.as_first().unwrap().lhs.as_metasymbol(),
+                Some(&Metasymbol::And)
+            );
+        }
+        kind => panic!("unexpected {:?}", kind),
+    }
+
+    let input = "else";
+    let all_of_the_span = KeywordSpan::from_start_len(0, input.len() as u32);
+    let result: Result<AST<KeywordVariable, KeywordSpan>, _> = common::parse(
+        &parser,
+        input,
+        &rules,
+        &KeywordVariable::Peek,
+        &all_of_the_span,
+    );
+    let error = result.unwrap_err();
+    assert!(error.kind.is_failure());
+    assert_eq!(error.farthest_failure.pos, Some(0));
+    assert_eq!(error.farthest_failure.to_string(), "expected \"if\" at 0");
+}
+
+/// ```
+/// Alone = & ! / f
+/// ```
+#[test]
+fn alone() {
+    let parser = KeywordParser;
+    let mut rules = HashMap::new();
+
+    rules.insert(
+        KeywordVariable::Alone,
+        RightRule::from_right_rule_kind(
+            (RightRuleKind::And, RightRuleKind::Not),
+            RightRuleKind::Failure,
+        ),
+    );
+
+    // `!` alone always fails, so `& !` fails.
+    let all_of_the_span = KeywordSpan::from_start_len(0, 0);
+    let result: Result<AST<KeywordVariable, KeywordSpan>, _> = common::parse(
+        &parser,
+        "",
+        &rules,
+        &KeywordVariable::Alone,
+        &all_of_the_span,
+    );
+    let error = result.unwrap_err();
+    assert!(error.kind.is_failure());
+    assert_eq!(error.farthest_failure.pos, None);
+}
+
+/// ```
+/// Checked = Check Body / f
+/// Check = ! Letter / f
+/// Body = Letter () / f
+/// Letter = 'a' () / f
+/// ```
+#[test]
+fn evaluated_inside_and_outside_not() {
+    let parser = KeywordParser;
+    let mut rules = HashMap::new();
+
+    rules.insert(
+        KeywordVariable::Checked,
+        RightRule::from_right_rule_kind(
+            (
+                RightRuleKind::V(KeywordVariable::Check),
+                RightRuleKind::V(KeywordVariable::Body),
+            ),
+            RightRuleKind::Failure,
+        ),
+    );
+    rules.insert(
+        KeywordVariable::Check,
+        RightRule::from_right_rule_kind(
+            (
+                RightRuleKind::Not,
+                RightRuleKind::V(KeywordVariable::Letter),
+            ),
+            RightRuleKind::Failure,
+        ),
+    );
+    rules.insert(
+        KeywordVariable::Body,
+        RightRule::from_right_rule_kind(
+            (
+                RightRuleKind::V(KeywordVariable::Letter),
+                RightRuleKind::Empty,
+            ),
+            RightRuleKind::Failure,
+        ),
+    );
+    rules.insert(
+        KeywordVariable::Letter,
+        RightRule::from_right_rule_kind(
+            (
+                RightRuleKind::T(StrTerminal::Char('a')),
+                RightRuleKind::Empty,
+            ),
+            RightRuleKind::Failure,
+        ),
+    );
+
+    // `Letter` fails inside `!` first, and then outside it, where 'a' is expected.
+    let input = "b";
+    let all_of_the_span = KeywordSpan::from_start_len(0, input.len() as u32);
+    let result: Result<AST<KeywordVariable, KeywordSpan>, _> = common::parse(
+        &parser,
+        input,
+        &rules,
+        &KeywordVariable::Checked,
+        &all_of_the_span,
+    );
+    let error = result.unwrap_err();
+    assert!(error.kind.is_failure());
+    assert_eq!(error.farthest_failure.to_string(), "expected 'a' at 0");
+}
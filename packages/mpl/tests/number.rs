@@ -1,7 +1,8 @@
 use mpl::input::Input;
 use mpl::output::Output;
-use mpl::parser::Parser;
+use mpl::parser::{ParseOptions, Parser};
 use mpl::position::Position;
+use mpl::profiler::Profiler;
 use mpl::rules::{RightRule, RightRuleKind, Rules};
 use mpl::span::{Len, Span, Start, StartAndLenSpan};
 use mpl::symbols::{Metasymbol, Terminal, Variable};
@@ -262,11 +263,42 @@ fn number() {
     let result = parser.parse(&input, &rules, &NumberVariable::Number, &all_of_the_span);
 
     assert!(result.is_err());
+    assert_eq!(
+        result,
+        parser.parse_memoized(&input, &rules, &NumberVariable::Number, &all_of_the_span)
+    );
+    assert_eq!(
+        result,
+        parser.parse_iterative(&input, &rules, &NumberVariable::Number, &all_of_the_span)
+    );
 
     let input = ExtStr(String::from("0１0０1"));
     // all of the span
     let all_of_the_span = StartAndLenSpan::from_start_len(BytePos(0), input.0.len() as u16);
     let result = parser.parse(&input, &rules, &NumberVariable::Number, &all_of_the_span);
 
+    assert_eq!(
+        result,
+        parser.parse_memoized(&input, &rules, &NumberVariable::Number, &all_of_the_span)
+    );
+    assert_eq!(
+        result,
+        parser.parse_iterative(&input, &rules, &NumberVariable::Number, &all_of_the_span)
+    );
     assert_eq!(result.unwrap().span.len, 9);
+
+    // Custom positions are profiled by their offsets.
+    let mut profiler = Profiler::<_, _, u16>::new(&input);
+    let result = parser.parse_memoized_with_observer(
+        &input,
+        &rules,
+        &NumberVariable::Number,
+        &all_of_the_span,
+        ParseOptions::default(),
+        &mut profiler,
+    );
+    assert!(result.is_ok());
+    let digit = profiler.get(&NumberVariable::Digit).unwrap();
+    assert_eq!(digit.successes, 5);
+    assert_eq!(digit.consumed, 9);
 }
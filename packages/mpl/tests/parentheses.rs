@@ -1,10 +1,14 @@
+use mpl::error::{Limit, ParseErrorKind};
+use mpl::observer::Tracer;
 use mpl::output::Output;
-use mpl::parser::Parser;
+use mpl::parser::{ParseOptions, ParseState, Parser};
+use mpl::profiler::{Profiler, VariableProfile};
 use mpl::rules::{RightRule, RightRuleKind, Rules};
 use mpl::span::{Len, Start, StartAndLenSpan};
-use mpl::symbols::{StrTerminal, U8SliceTerminal, Variable};
+use mpl::symbols::{StrTerminal, TerminalSymbol, U8SliceTerminal, Variable};
 use mpl::trees::{AST, CST};
 use std::collections::HashMap;
+use std::time::Instant;
 
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
 enum ParenthesesVariable {
@@ -15,6 +19,8 @@ enum ParenthesesVariable {
 
 impl Variable for ParenthesesVariable {}
 
+type ParenthesesAST<O = ()> = AST<ParenthesesVariable, StartAndLenSpan<u32, u16>, O>;
+
 enum ParseResult {
     Ok,
     Err,
@@ -46,32 +52,48 @@ where
 {
 }
 
+impl<'i> Output<'i, str, ParenthesesVariable, StartAndLenSpan<u32, u16>> for String {
+    fn output_ast(
+        _input: &'i str,
+        cst: CST<ParenthesesVariable, StartAndLenSpan<u32, u16>, Self>,
+    ) -> AST<ParenthesesVariable, StartAndLenSpan<u32, u16>, Self> {
+        match cst.node.value {
+            ParenthesesVariable::Open => AST::from_cst_and_output(cst, Some(String::from("open"))),
+            ParenthesesVariable::Parentheses => {
+                AST::from_cst_and_output(cst, Some(String::from("paren")))
+            }
+            ParenthesesVariable::Close => {
+                AST::from_cst_and_output(cst, Some(String::from("close")))
+            }
+        }
+    }
+}
+
+impl<'i, P, L, O>
+    Parser<
+        'i,
+        [u8],
+        U8SliceTerminal<'i>,
+        ParenthesesVariable,
+        StartAndLenSpan<P, L>,
+        P,
+        HashMap<ParenthesesVariable, RightRule<U8SliceTerminal<'i>, ParenthesesVariable>>,
+        O,
+    > for ParenthesesParser
+where
+    P: Start<[u8], L>,
+    L: Len<[u8], P>,
+    O: Output<'i, [u8], ParenthesesVariable, StartAndLenSpan<P, L>>,
+{
+}
+
 /// ```
 /// Open = '(' Parentheses / ()
 /// Parentheses = Open Close / f
 /// Close = ")" Open / f
 /// ```
-#[test]
-fn str_parentheses() {
-    impl<'i> Output<'i, str, ParenthesesVariable, StartAndLenSpan<u32, u16>> for String {
-        fn output_ast(
-            _input: &'i str,
-            cst: CST<ParenthesesVariable, StartAndLenSpan<u32, u16>, Self>,
-        ) -> AST<ParenthesesVariable, StartAndLenSpan<u32, u16>, Self> {
-            match cst.node.value {
-                ParenthesesVariable::Open => {
-                    AST::from_cst_and_output(cst, Some(String::from("open")))
-                }
-                ParenthesesVariable::Parentheses => {
-                    AST::from_cst_and_output(cst, Some(String::from("paren")))
-                }
-                ParenthesesVariable::Close => {
-                    AST::from_cst_and_output(cst, Some(String::from("close")))
-                }
-            }
-        }
-    }
-
+fn paren_rules(
+) -> HashMap<ParenthesesVariable, RightRule<StrTerminal<'static>, ParenthesesVariable>> {
     let mut rules = HashMap::new();
 
     rules.insert(
@@ -105,6 +127,13 @@ fn str_parentheses() {
         ),
     );
 
+    rules
+}
+
+#[test]
+fn str_parentheses() {
+    let rules = paren_rules();
+
     let parser = ParenthesesParser;
 
     for input in INPUTS {
@@ -113,15 +142,30 @@ fn str_parentheses() {
         // all of the span
         let all_of_the_span =
             StartAndLenSpan::<u32, u16>::from_start_len(0, input_data.len() as u16);
-        let result: Result<
-            AST<ParenthesesVariable, StartAndLenSpan<u32, u16>, String>,
-            AST<ParenthesesVariable, StartAndLenSpan<u32, u16>, String>,
-        > = parser.parse(
+        let result: Result<ParenthesesAST<String>, _> = parser.parse(
             input_data,
             &rules,
             &ParenthesesVariable::Open,
             &all_of_the_span,
         );
+        assert_eq!(
+            result,
+            parser.parse_memoized(
+                input_data,
+                &rules,
+                &ParenthesesVariable::Open,
+                &all_of_the_span,
+            )
+        );
+        assert_eq!(
+            result,
+            parser.parse_iterative(
+                input_data,
+                &rules,
+                &ParenthesesVariable::Open,
+                &all_of_the_span,
+            )
+        );
         match parse_result {
             ParseResult::Ok => assert!(result.is_ok()),
             ParseResult::Err => assert!(result.is_err()),
@@ -136,24 +180,6 @@ fn str_parentheses() {
 /// ```
 #[test]
 fn u8_slice_parentheses() {
-    impl<'i, P, L, O>
-        Parser<
-            'i,
-            [u8],
-            U8SliceTerminal<'i>,
-            ParenthesesVariable,
-            StartAndLenSpan<P, L>,
-            P,
-            HashMap<ParenthesesVariable, RightRule<U8SliceTerminal<'i>, ParenthesesVariable>>,
-            O,
-        > for ParenthesesParser
-    where
-        P: Start<[u8], L>,
-        L: Len<[u8], P>,
-        O: Output<'i, [u8], ParenthesesVariable, StartAndLenSpan<P, L>>,
-    {
-    }
-
     let mut rules = HashMap::new();
 
     rules.insert(
@@ -195,18 +221,320 @@ fn u8_slice_parentheses() {
         // all of the span
         let all_of_the_span =
             StartAndLenSpan::<u32, u16>::from_start_len(0, input_data.len() as u16);
-        let result: Result<
-            AST<ParenthesesVariable, StartAndLenSpan<u32, u16>, ()>,
-            AST<ParenthesesVariable, StartAndLenSpan<u32, u16>, ()>,
-        > = parser.parse(
+        let result: Result<ParenthesesAST, _> = parser.parse(
             input_data,
             &rules,
             &ParenthesesVariable::Open,
             &all_of_the_span,
         );
+        assert_eq!(
+            result,
+            parser.parse_memoized(
+                input_data,
+                &rules,
+                &ParenthesesVariable::Open,
+                &all_of_the_span,
+            )
+        );
+        assert_eq!(
+            result,
+            parser.parse_iterative(
+                input_data,
+                &rules,
+                &ParenthesesVariable::Open,
+                &all_of_the_span,
+            )
+        );
         match parse_result {
             ParseResult::Ok => assert!(result.is_ok()),
             ParseResult::Err => assert!(result.is_err()),
         }
     }
 }
+
+#[test]
+fn parse_error() {
+    let mut rules = paren_rules();
+    let close = rules.remove(&ParenthesesVariable::Close).unwrap();
+
+    let parser = ParenthesesParser;
+    let input = "(()";
+    let all_of_the_span = StartAndLenSpan::<u32, u16>::from_start_len(0, input.len() as u16);
+
+    // Missing rule
+    assert_eq!(
+        rules.missing_rules(&ParenthesesVariable::Open),
+        vec![ParenthesesVariable::Close]
+    );
+    let result: Result<ParenthesesAST, _> =
+        parser.parse(input, &rules, &ParenthesesVariable::Open, &all_of_the_span);
+    let error = result.unwrap_err();
+    assert_eq!(
+        error.kind,
+        ParseErrorKind::MissingRule(ParenthesesVariable::Close)
+    );
+
+    rules.insert(ParenthesesVariable::Close, close);
+    assert!(rules.missing_rules(&ParenthesesVariable::Open).is_empty());
+
+    // Partial match
+    let result: Result<ParenthesesAST, _> =
+        parser.parse(input, &rules, &ParenthesesVariable::Open, &all_of_the_span);
+    let error = result.unwrap_err();
+    assert!(error.kind.is_partial_match());
+    if let ParseErrorKind::PartialMatch { hi, .. } = error.kind {
+        assert_eq!(hi, 0);
+    }
+    assert_eq!(error.farthest_failure.pos, Some(3));
+    assert_eq!(
+        error.farthest_failure.stack,
+        vec![
+            ParenthesesVariable::Open,
+            ParenthesesVariable::Parentheses,
+            ParenthesesVariable::Open,
+            ParenthesesVariable::Parentheses,
+            ParenthesesVariable::Close,
+            ParenthesesVariable::Open,
+        ]
+    );
+    assert_eq!(
+        error.farthest_failure.expected,
+        vec![
+            TerminalSymbol::Original(StrTerminal::Char('(')),
+            TerminalSymbol::Original(StrTerminal::Str(")")),
+        ]
+    );
+    assert_eq!(error.to_string(), "expected '(' or \")\" at 3");
+}
+
+#[test]
+fn limits() {
+    let rules = paren_rules();
+
+    let parser = ParenthesesParser;
+    let input = "((()))";
+    let all_of_the_span = StartAndLenSpan::<u32, u16>::from_start_len(0, input.len() as u16);
+
+    // Steps of the parse
+    let mut state = ParseState::new();
+    let result: Result<ParenthesesAST, _> = parser.eval(
+        input,
+        &0,
+        &rules,
+        &ParenthesesVariable::Open,
+        &(input.len() as u32),
+        &mut state,
+    );
+    assert!(result.is_ok());
+    let steps = state.steps;
+
+    let result: Result<ParenthesesAST, _> = parser.parse_with_options(
+        input,
+        &rules,
+        &ParenthesesVariable::Open,
+        &all_of_the_span,
+        ParseOptions::new().max_steps(steps),
+    );
+    assert!(result.is_ok());
+
+    for (options, limit) in [
+        (ParseOptions::new().max_depth(4), Limit::Depth(4)),
+        (
+            ParseOptions::new().max_steps(steps - 1),
+            Limit::Steps(steps - 1),
+        ),
+        (
+            ParseOptions::new().deadline(Instant::now()),
+            Limit::Deadline,
+        ),
+    ] {
+        let result: Result<ParenthesesAST, _> = parser.parse_with_options(
+            input,
+            &rules,
+            &ParenthesesVariable::Open,
+            &all_of_the_span,
+            options,
+        );
+        let error = result.unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::LimitExceeded(limit));
+        assert_eq!(error.to_string(), limit.to_string());
+
+        // Memoized and iterative parses honor the same limits.
+        let result: Result<ParenthesesAST, _> = parser.parse_memoized_with_options(
+            input,
+            &rules,
+            &ParenthesesVariable::Open,
+            &all_of_the_span,
+            options,
+        );
+        assert_eq!(
+            result.unwrap_err().kind,
+            ParseErrorKind::LimitExceeded(limit)
+        );
+        let result: Result<ParenthesesAST, _> = parser.parse_iterative_with_options(
+            input,
+            &rules,
+            &ParenthesesVariable::Open,
+            &all_of_the_span,
+            options,
+        );
+        assert_eq!(
+            result.unwrap_err().kind,
+            ParseErrorKind::LimitExceeded(limit)
+        );
+
+        // So does an observed parse.
+        let result: Result<ParenthesesAST, _> = parser.parse_with_observer(
+            input,
+            &rules,
+            &ParenthesesVariable::Open,
+            &all_of_the_span,
+            options,
+            Tracer::new(Vec::new()),
+        );
+        assert_eq!(
+            result.unwrap_err().kind,
+            ParseErrorKind::LimitExceeded(limit)
+        );
+    }
+}
+
+#[test]
+fn trace() {
+    let rules = paren_rules();
+
+    let parser = ParenthesesParser;
+    let input = "()";
+    let all_of_the_span = StartAndLenSpan::<u32, u16>::from_start_len(0, input.len() as u16);
+
+    let mut tracer = Tracer::new(Vec::new());
+    let result: Result<ParenthesesAST, _> = parser.parse_with_observer(
+        input,
+        &rules,
+        &ParenthesesVariable::Open,
+        &all_of_the_span,
+        ParseOptions::default(),
+        &mut tracer,
+    );
+    assert!(result.is_ok());
+
+    let trace = String::from_utf8(tracer.into_inner()).unwrap();
+    assert_eq!(
+        trace,
+        "\
+Open at 0
+  first
+  '(' matched 0..1
+  Parentheses at 1
+    first
+    Open at 1
+      first
+      '(' failed at 1
+      second
+      () matched 1..1
+    Open matched 1..1
+    Close at 1
+      first
+      \")\" matched 1..2
+      Open at 2
+        first
+        '(' failed at 2
+        second
+        () matched 2..2
+      Open matched 2..2
+    Close matched 1..2
+  Parentheses matched 1..2
+Open matched 0..2
+"
+    );
+
+    // Memoized and iterative evaluation are observed in the same order, since nothing is reused.
+    let mut tracer = Tracer::new(Vec::new());
+    let result: Result<ParenthesesAST, _> = parser.parse_memoized_with_observer(
+        input,
+        &rules,
+        &ParenthesesVariable::Open,
+        &all_of_the_span,
+        ParseOptions::default(),
+        &mut tracer,
+    );
+    assert!(result.is_ok());
+    assert_eq!(String::from_utf8(tracer.into_inner()).unwrap(), trace);
+
+    let mut tracer = Tracer::new(Vec::new());
+    let result: Result<ParenthesesAST, _> = parser.parse_iterative_with_observer(
+        input,
+        &rules,
+        &ParenthesesVariable::Open,
+        &all_of_the_span,
+        ParseOptions::default(),
+        &mut tracer,
+    );
+    assert!(result.is_ok());
+    assert_eq!(String::from_utf8(tracer.into_inner()).unwrap(), trace);
+}
+
+#[test]
+fn profile() {
+    let rules = paren_rules();
+
+    let parser = ParenthesesParser;
+    let input = "()(";
+    let all_of_the_span = StartAndLenSpan::<u32, u16>::from_start_len(0, input.len() as u16);
+
+    let mut profiler = Profiler::<_, _, u16>::new(input);
+    let result: Result<ParenthesesAST, _> = parser.parse_with_observer(
+        input,
+        &rules,
+        &ParenthesesVariable::Open,
+        &all_of_the_span,
+        ParseOptions::default(),
+        &mut profiler,
+    );
+    assert!(result.unwrap_err().kind.is_partial_match());
+
+    assert_eq!(
+        profiler.report(),
+        vec![
+            (
+                ParenthesesVariable::Open,
+                VariableProfile {
+                    invocations: 4,
+                    memo_hits: 0,
+                    successes: 4,
+                    failures: 0,
+                    backtracks: 3,
+                    consumed: 2,
+                    rescanned: 1,
+                }
+            ),
+            (
+                ParenthesesVariable::Parentheses,
+                VariableProfile {
+                    invocations: 2,
+                    memo_hits: 0,
+                    successes: 1,
+                    failures: 1,
+                    backtracks: 1,
+                    consumed: 1,
+                    rescanned: 0,
+                }
+            ),
+            (
+                ParenthesesVariable::Close,
+                VariableProfile {
+                    invocations: 2,
+                    memo_hits: 0,
+                    successes: 1,
+                    failures: 1,
+                    backtracks: 1,
+                    consumed: 1,
+                    rescanned: 0,
+                }
+            ),
+        ]
+    );
+    assert!(profiler
+        .to_string()
+        .starts_with("variable                  invocations"));
+}
@@ -0,0 +1,128 @@
+use mpl::output::Output;
+use mpl::parser::Parser;
+use mpl::rules::{RightRule, RightRuleKind, Rules};
+use mpl::span::{Len, Start, StartAndLenSpan};
+use mpl::symbols::{Predicate, U8SliceTerminal, Variable};
+use mpl::trees::AST;
+use std::collections::HashMap;
+
+mod common;
+
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+enum AsciiVariable {
+    Ascii,
+    Number,
+}
+
+impl Variable for AsciiVariable {}
+
+type AsciiSpan = StartAndLenSpan<u32, u32>;
+
+struct AsciiParser;
+
+impl<'i, V, P, L, R, O> Parser<'i, [u8], U8SliceTerminal<'i>, V, StartAndLenSpan<P, L>, P, R, O>
+    for AsciiParser
+where
+    V: Variable,
+    P: Start<[u8], L>,
+    L: Len<[u8], P>,
+    R: Rules<U8SliceTerminal<'i>, V>,
+    O: Output<'i, [u8], V, StartAndLenSpan<P, L>>,
+{
+}
+
+/// Any byte where `b & 0x80 == 0`.
+static ASCII_BYTE: Predicate<[u8]> = Predicate::new(|rest| match rest.first() {
+    Some(b) if b & 0x80 == 0 => Some(1),
+    _ => None,
+});
+
+/// One or more ASCII digits.
+static DIGITS: Predicate<[u8]> = Predicate::new(|rest| {
+    let len = rest.iter().take_while(|b| b.is_ascii_digit()).count();
+    Some(len).filter(|&len| len != 0)
+});
+
+/// ```
+/// Ascii = ASCII_BYTE Ascii / ()
+/// ```
+#[test]
+fn byte_predicate() {
+    let parser = AsciiParser;
+    let mut rules = HashMap::new();
+
+    rules.insert(
+        AsciiVariable::Ascii,
+        RightRule::from_right_rule_kind(
+            (
+                RightRuleKind::T(U8SliceTerminal::Predicate(&ASCII_BYTE)),
+                RightRuleKind::V(AsciiVariable::Ascii),
+            ),
+            RightRuleKind::Empty,
+        ),
+    );
+
+    let parse = |input: &'static [u8]| -> Result<AST<AsciiVariable, AsciiSpan>, _> {
+        let all_of_the_span = AsciiSpan::from_start_len(0, input.len() as u32);
+        common::parse(
+            &parser,
+            input,
+            &rules,
+            &AsciiVariable::Ascii,
+            &all_of_the_span,
+        )
+    };
+
+    assert!(parse(b"ascii").is_ok());
+
+    let error = parse(b"a\xffb").unwrap_err();
+    assert!(error.kind.is_partial_match());
+    assert_eq!(
+        error.farthest_failure.to_string(),
+        "expected predicate at 1"
+    );
+}
+
+/// ```
+/// Number = DIGITS () / f
+/// ```
+#[test]
+fn variable_length() {
+    let parser = AsciiParser;
+    let mut rules = HashMap::new();
+
+    rules.insert(
+        AsciiVariable::Number,
+        RightRule::from_right_rule_kind(
+            (
+                RightRuleKind::T(U8SliceTerminal::Predicate(&DIGITS)),
+                RightRuleKind::Empty,
+            ),
+            RightRuleKind::Failure,
+        ),
+    );
+
+    let parse = |input: &'static [u8]| -> Result<AST<AsciiVariable, AsciiSpan>, _> {
+        let all_of_the_span = AsciiSpan::from_start_len(0, input.len() as u32);
+        common::parse(
+            &parser,
+            input,
+            &rules,
+            &AsciiVariable::Number,
+            &all_of_the_span,
+        )
+    };
+
+    let ast = parse(b"2024").unwrap();
+    assert_eq!(
+        ast.as_first().unwrap().lhs.span,
+        AsciiSpan::from_start_len(0, 4)
+    );
+
+    let error = parse(b"x").unwrap_err();
+    assert!(error.kind.is_failure());
+    assert_eq!(
+        error.farthest_failure.to_string(),
+        "expected predicate at 0"
+    );
+}
@@ -0,0 +1,164 @@
+use mpl::error::ParseErrorKind;
+use mpl::output::Output;
+use mpl::parser::Parser;
+use mpl::rules::{RightRule, RightRuleKind, Rules};
+use mpl::span::{Len, Span, Start, StartAndLenSpan};
+use mpl::symbols::{U8SliceTerminal, Variable};
+use mpl::trees::{AST, CST};
+use std::collections::HashMap;
+use std::convert::TryInto;
+
+mod common;
+
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+enum RiffVariable {
+    Chunks,
+    Chunk,
+    SizedData,
+    Size,
+    Data,
+}
+
+impl Variable for RiffVariable {}
+
+/// Size of chunk data.
+#[derive(Clone, Debug, PartialEq)]
+struct Size(u32);
+
+type RiffSpan = StartAndLenSpan<u32, u32>;
+
+type RiffAST = AST<RiffVariable, RiffSpan, Size>;
+
+impl<'i> Output<'i, [u8], RiffVariable, RiffSpan> for Size {
+    fn output_ast(input: &'i [u8], cst: CST<RiffVariable, RiffSpan, Self>) -> RiffAST {
+        match cst.node.value {
+            RiffVariable::Size => {
+                let lo = cst.span.start as usize;
+                let hi = cst.span.hi(input) as usize;
+
+                let n = u32::from_le_bytes(input[lo..hi].try_into().unwrap());
+
+                AST::from_cst_and_output(cst, Some(Size(n)))
+            }
+            _ => AST::from_cst(cst),
+        }
+    }
+
+    fn len_of_next(&self) -> Option<usize> {
+        Some(self.0 as usize)
+    }
+}
+
+struct RiffParser;
+
+impl<'i, V, P, L, R, O> Parser<'i, [u8], U8SliceTerminal<'i>, V, StartAndLenSpan<P, L>, P, R, O>
+    for RiffParser
+where
+    V: Variable,
+    P: Start<[u8], L>,
+    L: Len<[u8], P>,
+    R: Rules<U8SliceTerminal<'i>, V>,
+    O: Output<'i, [u8], V, StartAndLenSpan<P, L>>,
+{
+}
+
+/// `Data` is evaluated exactly over the bytes of `Size`.
+///
+/// ```
+/// Chunks = Chunk Chunks / ()
+/// Chunk = ???? SizedData / f
+/// SizedData = Size Data / f
+/// Size = ???? () / f
+/// Data = * () / f
+/// ```
+#[test]
+fn sized_chunks() {
+    let parser = RiffParser;
+    let mut rules = HashMap::new();
+
+    rules.insert(
+        RiffVariable::Chunks,
+        RightRule::from_right_rule_kind(
+            (
+                RightRuleKind::V(RiffVariable::Chunk),
+                RightRuleKind::V(RiffVariable::Chunks),
+            ),
+            RightRuleKind::Empty,
+        ),
+    );
+    rules.insert(
+        RiffVariable::Chunk,
+        RightRule::from_right_rule_kind(
+            (
+                RightRuleKind::Any(4),
+                RightRuleKind::V(RiffVariable::SizedData),
+            ),
+            RightRuleKind::Failure,
+        ),
+    );
+    rules.insert(
+        RiffVariable::SizedData,
+        RightRule::from_right_rule_kind(
+            (
+                RightRuleKind::V(RiffVariable::Size),
+                RightRuleKind::V(RiffVariable::Data),
+            ),
+            RightRuleKind::Failure,
+        ),
+    );
+    rules.insert(
+        RiffVariable::Size,
+        RightRule::from_right_rule_kind(
+            (RightRuleKind::Any(4), RightRuleKind::Empty),
+            RightRuleKind::Failure,
+        ),
+    );
+    rules.insert(
+        RiffVariable::Data,
+        RightRule::from_right_rule_kind(
+            (RightRuleKind::All, RightRuleKind::Empty),
+            RightRuleKind::Failure,
+        ),
+    );
+
+    let parse = |input: &'static [u8]| -> Result<RiffAST, _> {
+        let all_of_the_span = RiffSpan::from_start_len(0, input.len() as u32);
+        common::parse(
+            &parser,
+            input,
+            &rules,
+            &RiffVariable::Chunks,
+            &all_of_the_span,
+        )
+    };
+
+    let ast = parse(b"fmt \x02\x00\x00\x00\x01\x02data\x03\x00\x00\x00abc").unwrap();
+
+    let first = ast.as_first().unwrap();
+    assert_eq!(first.lhs.span, RiffSpan::from_start_len(0, 10));
+    let second_chunk = &first.rhs.as_first().unwrap().lhs;
+    assert_eq!(second_chunk.span, RiffSpan::from_start_len(10, 11));
+
+    let sized_data = &second_chunk.as_first().unwrap().rhs;
+    let data = &sized_data.as_first().unwrap().rhs;
+    assert_eq!(data.span, RiffSpan::from_start_len(18, 3));
+
+    // The second chunk has 3 bytes of 5.
+    let error = parse(b"fmt \x02\x00\x00\x00\x01\x02data\x05\x00\x00\x00abc").unwrap_err();
+    match error.kind {
+        ParseErrorKind::PartialMatch { hi, .. } => assert_eq!(hi, 10),
+        kind => panic!("unexpected {:?}", kind),
+    }
+
+    // The sizes of the second chunk are larger than the input, and the largest one overflows `u32`.
+    for input in [
+        &b"fmt \x02\x00\x00\x00\x01\x02data\x00\x01\x00\x00abc"[..],
+        &b"fmt \x02\x00\x00\x00\x01\x02data\xff\xff\xff\xffabc"[..],
+    ] {
+        let error = parse(input).unwrap_err();
+        match error.kind {
+            ParseErrorKind::PartialMatch { hi, .. } => assert_eq!(hi, 10),
+            kind => panic!("unexpected {:?}", kind),
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use mpl::output::Output;
+use mpl::parser::Parser;
+use mpl::rules::{RightRule, RightRuleKind, Rules};
+use mpl::span::{Len, Span, Start, StartAndLenSpan};
+use mpl::symbols::{varint, U8SliceTerminal, Variable};
+use mpl::trees::{AST, CST};
+use std::collections::HashMap;
+
+mod common;
+
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+enum VarintVariable {
+    // WebAssembly
+    Unsigned,
+    Signed,
+    // MIDI
+    DeltaTime,
+}
+
+impl Variable for VarintVariable {}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Value {
+    U64(u64),
+    I64(i64),
+    U32(u32),
+}
+
+type VarintSpan = StartAndLenSpan<u32, u16>;
+
+type VarintAST = AST<VarintVariable, VarintSpan, Value>;
+
+/// Decodes the value of the variable.
+impl<'i> Output<'i, [u8], VarintVariable, VarintSpan> for Value {
+    fn output_ast(input: &'i [u8], cst: CST<VarintVariable, VarintSpan, Self>) -> VarintAST {
+        let bytes = &input[cst.span.start as usize..cst.span.hi(input) as usize];
+        let value = match cst.node.value {
+            VarintVariable::Unsigned => varint::uleb128(bytes).map(|(n, _)| Value::U64(n)),
+            VarintVariable::Signed => varint::sleb128(bytes).map(|(n, _)| Value::I64(n)),
+            VarintVariable::DeltaTime => varint::vlq(bytes).map(|(n, _)| Value::U32(n)),
+        };
+
+        AST::from_cst_and_output(cst, value)
+    }
+}
+
+struct VarintParser;
+
+impl<'i, V, P, L, R, O> Parser<'i, [u8], U8SliceTerminal<'i>, V, StartAndLenSpan<P, L>, P, R, O>
+    for VarintParser
+where
+    V: Variable,
+    P: Start<[u8], L>,
+    L: Len<[u8], P>,
+    R: Rules<U8SliceTerminal<'i>, V>,
+    O: Output<'i, [u8], V, StartAndLenSpan<P, L>>,
+{
+}
+
+fn output(ast: VarintAST) -> Option<Value> {
+    ast.into_internal().and_then(|internal| internal.value.1)
+}
+
+/// ```
+/// Unsigned = ULEB128 () / f
+/// Signed = SLEB128 () / f
+/// DeltaTime = VLQ () / f
+/// ```
+#[test]
+fn decoded_value() {
+    let parser = VarintParser;
+    let mut rules = HashMap::new();
+
+    for (variable, terminal) in [
+        (VarintVariable::Unsigned, U8SliceTerminal::Uleb128),
+        (VarintVariable::Signed, U8SliceTerminal::Sleb128),
+        (VarintVariable::DeltaTime, U8SliceTerminal::Vlq),
+    ] {
+        rules.insert(
+            variable,
+            RightRule::from_right_rule_kind(
+                (RightRuleKind::T(terminal), RightRuleKind::Empty),
+                RightRuleKind::Failure,
+            ),
+        );
+    }
+
+    let parse = |input: &'static [u8], start_variable| -> Result<VarintAST, _> {
+        let all_of_the_span = VarintSpan::from_start_len(0, input.len() as u16);
+        common::parse(&parser, input, &rules, start_variable, &all_of_the_span)
+    };
+
+    let ast = parse(&[0xe5, 0x8e, 0x26], &VarintVariable::Unsigned).unwrap();
+    assert_eq!(ast.span, VarintSpan::from_start_len(0, 3));
+    assert_eq!(output(ast), Some(Value::U64(624485)));
+
+    let ast = parse(&[0xc0, 0xbb, 0x78], &VarintVariable::Signed).unwrap();
+    assert_eq!(output(ast), Some(Value::I64(-123456)));
+
+    let ast = parse(&[0xff, 0x7f], &VarintVariable::DeltaTime).unwrap();
+    assert_eq!(output(ast), Some(Value::U32(0x3fff)));
+}
+
+/// ```
+/// Unsigned = ULEB128 () / f
+/// Signed = SLEB128 () / f
+/// DeltaTime = VLQ () / f
+/// ```
+#[test]
+fn invalid_encoding() {
+    let parser = VarintParser;
+    let mut rules = HashMap::new();
+
+    for (variable, terminal) in [
+        (VarintVariable::Unsigned, U8SliceTerminal::Uleb128),
+        (VarintVariable::Signed, U8SliceTerminal::Sleb128),
+        (VarintVariable::DeltaTime, U8SliceTerminal::Vlq),
+    ] {
+        rules.insert(
+            variable,
+            RightRule::from_right_rule_kind(
+                (RightRuleKind::T(terminal), RightRuleKind::Empty),
+                RightRuleKind::Failure,
+            ),
+        );
+    }
+
+    let parse = |input: &'static [u8], start_variable| -> Result<VarintAST, _> {
+        let all_of_the_span = VarintSpan::from_start_len(0, input.len() as u16);
+        common::parse(&parser, input, &rules, start_variable, &all_of_the_span)
+    };
+
+    // Truncated
+    let error = parse(&[0x80], &VarintVariable::Unsigned).unwrap_err();
+    assert!(error.kind.is_failure());
+    assert_eq!(error.farthest_failure.to_string(), "expected ULEB128 at 0");
+
+    // Overlong
+    let error = parse(&[0x80, 0x00], &VarintVariable::Unsigned).unwrap_err();
+    assert!(error.kind.is_failure());
+    let error = parse(&[0xff, 0x7f], &VarintVariable::Signed).unwrap_err();
+    assert!(error.kind.is_failure());
+    let error = parse(&[0x80, 0x7f], &VarintVariable::DeltaTime).unwrap_err();
+    assert!(error.kind.is_failure());
+
+    // The rest of input is not part of the integer.
+    let error = parse(&[0x7f, 0x00], &VarintVariable::Unsigned).unwrap_err();
+    assert!(error.kind.is_partial_match());
+}
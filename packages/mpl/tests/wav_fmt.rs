@@ -0,0 +1,194 @@
+use mpl::output::Output;
+use mpl::parser::Parser;
+use mpl::rules::{RightRule, RightRuleKind, Rules};
+use mpl::span::{Len, Start, StartAndLenSpan};
+use mpl::symbols::{U8SliceTerminal, Variable};
+use mpl::trees::AST;
+use std::collections::HashMap;
+
+mod common;
+
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+enum WavFmtVariable {
+    // fmt chunk
+    Fmt,
+    ChunkSize,
+    AudioFormat,
+    NumChannels,
+    SampleRate,
+    ByteRateAndBlockAlign,
+    BitsPerSample,
+}
+
+impl Variable for WavFmtVariable {}
+
+type WavFmtSpan = StartAndLenSpan<u32, u16>;
+
+struct WavFmtParser;
+
+impl<'i, V, P, L, R, O> Parser<'i, [u8], U8SliceTerminal<'i>, V, StartAndLenSpan<P, L>, P, R, O>
+    for WavFmtParser
+where
+    V: Variable,
+    P: Start<[u8], L>,
+    L: Len<[u8], P>,
+    R: Rules<U8SliceTerminal<'i>, V>,
+    O: Output<'i, [u8], V, StartAndLenSpan<P, L>>,
+{
+}
+
+fn fmt_chunk(audio_format: u16, sample_rate: u32, bits_per_sample: u16) -> Vec<u8> {
+    let mut chunk = b"fmt ".to_vec();
+    chunk.extend_from_slice(&16u32.to_le_bytes());
+    chunk.extend_from_slice(&audio_format.to_le_bytes());
+    chunk.extend_from_slice(&2u16.to_le_bytes());
+    chunk.extend_from_slice(&sample_rate.to_le_bytes());
+    chunk.extend_from_slice(&(sample_rate * 4).to_le_bytes());
+    chunk.extend_from_slice(&4u16.to_le_bytes());
+    chunk.extend_from_slice(&bits_per_sample.to_le_bytes());
+    chunk
+}
+
+/// Each variable matches its field and the following fields.
+///
+/// ```
+/// Fmt = "fmt " ChunkSize / f
+/// ChunkSize = LEu32(16) AudioFormat / f
+/// // PCM, ADPCM or IEEE float
+/// AudioFormat = LEu16Range(1, 3) NumChannels / f
+/// NumChannels = LEu16Range(1, 8) SampleRate / f
+/// SampleRate = LEu32Range(8000, 192000) ByteRateAndBlockAlign / f
+/// ByteRateAndBlockAlign = ?????? BitsPerSample / f
+/// // A multiple of 8
+/// BitsPerSample = LEu16Mask(0x7, 0) () / f
+/// ```
+#[test]
+fn checked_fields() {
+    let parser = WavFmtParser;
+    let mut rules = HashMap::new();
+
+    rules.insert(
+        WavFmtVariable::Fmt,
+        RightRule::from_right_rule_kind(
+            (
+                RightRuleKind::T(U8SliceTerminal::Str("fmt ")),
+                RightRuleKind::V(WavFmtVariable::ChunkSize),
+            ),
+            RightRuleKind::Failure,
+        ),
+    );
+    rules.insert(
+        WavFmtVariable::ChunkSize,
+        RightRule::from_right_rule_kind(
+            (
+                RightRuleKind::T(U8SliceTerminal::LEu32(16)),
+                RightRuleKind::V(WavFmtVariable::AudioFormat),
+            ),
+            RightRuleKind::Failure,
+        ),
+    );
+    rules.insert(
+        WavFmtVariable::AudioFormat,
+        RightRule::from_right_rule_kind(
+            (
+                RightRuleKind::T(U8SliceTerminal::LEu16Range(1, 3)),
+                RightRuleKind::V(WavFmtVariable::NumChannels),
+            ),
+            RightRuleKind::Failure,
+        ),
+    );
+    rules.insert(
+        WavFmtVariable::NumChannels,
+        RightRule::from_right_rule_kind(
+            (
+                RightRuleKind::T(U8SliceTerminal::LEu16Range(1, 8)),
+                RightRuleKind::V(WavFmtVariable::SampleRate),
+            ),
+            RightRuleKind::Failure,
+        ),
+    );
+    rules.insert(
+        WavFmtVariable::SampleRate,
+        RightRule::from_right_rule_kind(
+            (
+                RightRuleKind::T(U8SliceTerminal::LEu32Range(8000, 192000)),
+                RightRuleKind::V(WavFmtVariable::ByteRateAndBlockAlign),
+            ),
+            RightRuleKind::Failure,
+        ),
+    );
+    rules.insert(
+        WavFmtVariable::ByteRateAndBlockAlign,
+        RightRule::from_right_rule_kind(
+            (
+                RightRuleKind::Any(6),
+                RightRuleKind::V(WavFmtVariable::BitsPerSample),
+            ),
+            RightRuleKind::Failure,
+        ),
+    );
+    rules.insert(
+        WavFmtVariable::BitsPerSample,
+        RightRule::from_right_rule_kind(
+            (
+                RightRuleKind::T(U8SliceTerminal::LEu16Mask(0x7, 0)),
+                RightRuleKind::Empty,
+            ),
+            RightRuleKind::Failure,
+        ),
+    );
+
+    // Every chunk is 24 bytes.
+    let all_of_the_span = WavFmtSpan::from_start_len(0, 24);
+
+    let chunk = fmt_chunk(1, 44100, 16);
+    let result: Result<AST<WavFmtVariable, WavFmtSpan>, _> = common::parse(
+        &parser,
+        &chunk,
+        &rules,
+        &WavFmtVariable::Fmt,
+        &all_of_the_span,
+    );
+    assert!(result.is_ok());
+
+    // Unknown audio format
+    let chunk = fmt_chunk(0xfffe, 44100, 16);
+    let result: Result<AST<WavFmtVariable, WavFmtSpan>, _> = common::parse(
+        &parser,
+        &chunk,
+        &rules,
+        &WavFmtVariable::Fmt,
+        &all_of_the_span,
+    );
+    let error = result.unwrap_err();
+    assert!(error.kind.is_failure());
+    assert_eq!(error.farthest_failure.pos, Some(8));
+
+    // Sample rate is too low.
+    let chunk = fmt_chunk(1, 4000, 16);
+    let result: Result<AST<WavFmtVariable, WavFmtSpan>, _> = common::parse(
+        &parser,
+        &chunk,
+        &rules,
+        &WavFmtVariable::Fmt,
+        &all_of_the_span,
+    );
+    let error = result.unwrap_err();
+    assert_eq!(error.farthest_failure.pos, Some(12));
+
+    // Bits per sample is not a multiple of 8.
+    let chunk = fmt_chunk(1, 44100, 12);
+    let result: Result<AST<WavFmtVariable, WavFmtSpan>, _> = common::parse(
+        &parser,
+        &chunk,
+        &rules,
+        &WavFmtVariable::Fmt,
+        &all_of_the_span,
+    );
+    let error = result.unwrap_err();
+    assert_eq!(error.farthest_failure.pos, Some(22));
+    assert_eq!(
+        error.farthest_failure.to_string(),
+        "expected LEu16Mask(7, 0) at 22"
+    );
+}
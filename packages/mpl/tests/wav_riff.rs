@@ -16,7 +16,8 @@ enum WavRiffVariable {
     U32,
 }
 
-#[derive(Debug)]
+#[allow(dead_code)]
+#[derive(Clone, Debug, PartialEq)]
 enum U16OrU32 {
     // U16(u16),
     U32(u32),
@@ -24,6 +25,8 @@ enum U16OrU32 {
 
 impl Variable for WavRiffVariable {}
 
+type WavRiffAST = AST<WavRiffVariable, StartAndLenSpan<u32, u16>, U16OrU32>;
+
 impl<'i> Output<'i, [u8], WavRiffVariable, StartAndLenSpan<u32, u16>> for U16OrU32 {
     fn output_ast(
         input: &'i [u8],
@@ -41,6 +44,13 @@ impl<'i> Output<'i, [u8], WavRiffVariable, StartAndLenSpan<u32, u16>> for U16OrU
             _ => AST::from_cst(cst),
         }
     }
+
+    /// `U32` is the size of the rest.
+    fn len_of_next(&self) -> Option<usize> {
+        match self {
+            U16OrU32::U32(n) => Some(*n as usize),
+        }
+    }
 }
 
 struct WavRiffParser;
@@ -115,11 +125,17 @@ fn wav_riff() {
     // all of the span
     let all_of_the_span = StartAndLenSpan::<u32, u16>::from_start_len(0, input.len() as u16);
 
-    let result: Result<
-        AST<WavRiffVariable, StartAndLenSpan<u32, u16>, U16OrU32>,
-        AST<WavRiffVariable, StartAndLenSpan<u32, u16>, U16OrU32>,
-    > = parser.parse(input, &rules, &WavRiffVariable::Riff, &all_of_the_span);
+    let result: Result<WavRiffAST, _> =
+        parser.parse(input, &rules, &WavRiffVariable::Riff, &all_of_the_span);
 
+    assert_eq!(
+        result,
+        parser.parse_memoized(input, &rules, &WavRiffVariable::Riff, &all_of_the_span)
+    );
+    assert_eq!(
+        result,
+        parser.parse_iterative(input, &rules, &WavRiffVariable::Riff, &all_of_the_span)
+    );
     assert!(result.is_ok());
 
     let input: &[u8] = &[
@@ -128,10 +144,47 @@ fn wav_riff() {
     // all of the span
     let all_of_the_span = StartAndLenSpan::<u32, u16>::from_start_len(0, input.len() as u16);
 
-    let result: Result<
-        AST<WavRiffVariable, StartAndLenSpan<u32, u16>, U16OrU32>,
-        AST<WavRiffVariable, StartAndLenSpan<u32, u16>, U16OrU32>,
-    > = parser.parse(input, &rules, &WavRiffVariable::Riff, &all_of_the_span);
+    let result: Result<WavRiffAST, _> =
+        parser.parse(input, &rules, &WavRiffVariable::Riff, &all_of_the_span);
 
+    assert_eq!(
+        result,
+        parser.parse_memoized(input, &rules, &WavRiffVariable::Riff, &all_of_the_span)
+    );
+    assert_eq!(
+        result,
+        parser.parse_iterative(input, &rules, &WavRiffVariable::Riff, &all_of_the_span)
+    );
     assert!(result.is_err());
+
+    // Truncated file size
+    let input: &[u8] = &[0x52, 0x49, 0x46, 0x46, 0x04, 0x00][..];
+    // all of the span
+    let all_of_the_span = StartAndLenSpan::<u32, u16>::from_start_len(0, input.len() as u16);
+
+    let result: Result<WavRiffAST, _> =
+        parser.parse(input, &rules, &WavRiffVariable::Riff, &all_of_the_span);
+
+    assert_eq!(result.unwrap_err().to_string(), "expected ???? at 4");
+
+    // File size is larger than "WAVE".
+    let input: &[u8] = &[
+        0x52, 0x49, 0x46, 0x46, 0x08, 0x00, 0x00, 0x00, 0x57, 0x41, 0x56, 0x45, 0x00, 0x00, 0x00,
+        0x00,
+    ][..];
+    // all of the span
+    let all_of_the_span = StartAndLenSpan::<u32, u16>::from_start_len(0, input.len() as u16);
+
+    let result: Result<WavRiffAST, _> =
+        parser.parse(input, &rules, &WavRiffVariable::Riff, &all_of_the_span);
+
+    assert_eq!(
+        result,
+        parser.parse_memoized(input, &rules, &WavRiffVariable::Riff, &all_of_the_span)
+    );
+    assert_eq!(
+        result,
+        parser.parse_iterative(input, &rules, &WavRiffVariable::Riff, &all_of_the_span)
+    );
+    assert!(result.unwrap_err().kind.is_failure());
 }